@@ -0,0 +1,13 @@
+extern crate raw_window_handle;
+extern crate window;
+extern crate winit_window;
+
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use window::WindowSettings;
+use winit_window::WinitWindow;
+
+fn main() {
+    let window = WinitWindow::new(&WindowSettings::new("Winit Window", (640, 480)));
+    println!("Window handle: {:?}", window.raw_window_handle());
+    println!("Display handle: {:?}", window.raw_display_handle());
+}
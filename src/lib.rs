@@ -1,35 +1,107 @@
 //! A [winit](https://crates.io/crates/winit) window back-end for the Piston game engine.
+//!
+//! # Platform support
+//!
+//! This backend only manages the window and its events. It doesn't create a rendering surface or
+//!  present frames, so presentation preferences like `vsync` and `hdr_requested` are recorded
+//!  for the renderer built on top of it to query.
+//!
+//! Some window features are only available on certain platforms. Their setters still store the
+//!  value everywhere else, so the matching getter reports what was last asked for. Each setter
+//!  says where it takes effect.
 
 extern crate input;
+extern crate raw_window_handle;
 #[cfg(feature = "use-vulkano")]
 extern crate vulkano;
 #[cfg(feature = "use-vulkano")]
 extern crate vulkano_win;
 extern crate window;
 extern crate winit;
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+))]
+extern crate x11_dl;
 
+#[cfg(feature = "ui-input")]
+mod ui_input;
 #[cfg(feature = "use-vulkano")]
 mod vulkano_window;
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+))]
+mod x11;
 
+// There's no X11 on these platforms, so there's never a connection to make requests on
+#[cfg(not(all(
+    unix,
+    not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+)))]
+mod x11 {
+    use winit::window::Window;
+
+    pub struct X11;
+
+    impl X11 {
+        pub fn new(_window: &Window) -> Option<X11> {
+            None
+        }
+
+        pub fn reset_screen_saver(&self) {}
+
+        pub fn set_opacity(&self, _opacity: f32) {}
+
+        pub fn set_visible_on_all_workspaces(&self, _value: bool) {}
+
+        pub fn set_transient_for(&self, _parent: &X11) {}
+    }
+
+    pub fn is_x11(_window: &Window) -> bool {
+        false
+    }
+}
+
+#[cfg(feature = "ui-input")]
+pub use ui_input::UiInput;
 #[cfg(feature = "use-vulkano")]
 pub use vulkano_window::{required_extensions, VulkanoWindow};
 
 use input::{
-    Button, ButtonArgs, ButtonState, CloseArgs, Event, Input, Key, Motion, MouseButton, ResizeArgs,
+    event_id::EventId, Button, ButtonArgs, ButtonState, CloseArgs, Event, Input, Key, Loop, Motion,
+    MouseButton, RenderArgs, ResizeArgs, Touch, TouchArgs,
+};
+use raw_window_handle::{
+    HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
+};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt,
+    hash::{Hash, Hasher},
+    panic,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use std::{collections::VecDeque, error::Error, time::Duration};
 use window::{AdvancedWindow, BuildFromWindowSettings, Position, Size, Window, WindowSettings};
 use winit::{
-    dpi::{LogicalPosition, LogicalSize, PhysicalPosition},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
     event::{
-        ElementState, KeyboardInput, MouseButton as WinitMouseButton, MouseScrollDelta,
-        VirtualKeyCode, WindowEvent,
+        ElementState, Ime, KeyboardInput, ModifiersState, MouseButton as WinitMouseButton,
+        MouseScrollDelta, Touch as WinitTouch, TouchPhase, VirtualKeyCode, WindowEvent,
     },
     event_loop::{ControlFlow, EventLoop},
+    monitor::MonitorHandle,
     platform::run_return::EventLoopExtRunReturn,
-    window::{CursorGrabMode, WindowBuilder},
+    window::{
+        BadIcon, CursorGrabMode, CursorIcon as WinitCursorIcon, Fullscreen, Icon, WindowBuilder,
+    },
 };
 
+type FileDropHandler = dyn FnMut(Vec<PathBuf>, Position);
+
 pub struct WinitWindow {
     // TODO: These public fields should be changed to accessors
     pub event_loop: EventLoop<UserEvent>,
@@ -37,13 +109,333 @@ pub struct WinitWindow {
     window: winit::window::Window,
 
     should_close: bool,
-    queued_events: VecDeque<Event>,
+    shut_down: bool,
+    pumping_enabled: bool,
+    window_cache: WindowCache,
+    queue: EventQueue,
     last_cursor: LogicalPosition<f64>,
     cursor_accumulator: LogicalPosition<f64>,
+    cursor_inside: bool,
+    cursor_physical: PhysicalPosition<f64>,
+    ime_preedit: String,
+    ime_purpose: ImePurpose,
+    modifiers: ModifiersState,
+    // Modifiers whose keys sent real key events during the current pump
+    modifier_keys_seen: ModifiersState,
+    modifiers_changed: bool,
 
     title: String,
+    app_name: String,
     capture_cursor: bool,
+    capture_center: [f64; 2],
+    pre_capture_cursor: Option<PhysicalPosition<f64>>,
+    skip_capture_warp: bool,
+    focus_on_capture: bool,
+    cursor_grab_mode: CursorGrab,
+    capture_grab: CursorGrab,
     exit_on_esc: bool,
+    input_options: InputOptions,
+    vsync: bool,
+    vsync_fallback_limiter: bool,
+    last_swap: Option<Instant>,
+    opacity: f32,
+    progress: Option<f64>,
+    visible_on_all_workspaces: bool,
+    keep_awake: bool,
+    last_awake_reset: Option<Instant>,
+    x11: Option<x11::X11>,
+    hdr_requested: bool,
+    occluded: bool,
+    theme: Theme,
+    scale_factor: f64,
+    continuous_rendering: bool,
+    render_events: bool,
+    last_render: Option<Instant>,
+    key_repeat: KeyRepeat,
+    suppress_button_for_text: bool,
+    text_keys: TextKeys,
+    monitor: Option<MonitorHandle>,
+    monitor_dirty: bool,
+    last_moved: Option<Position>,
+    requested_position: Option<Position>,
+    windowed_size: winit::dpi::Size,
+    resizable: bool,
+    resizable_locked: bool,
+    decorations: bool,
+    transparent: bool,
+    blur: bool,
+    dropped_files: Vec<PathBuf>,
+    file_drop_handler: Option<Box<FileDropHandler>>,
+
+    last_error: Option<String>,
+    last_pump_stats: PumpStats,
+    resize_debounce: Duration,
+    last_resize: Option<Instant>,
+    pending_resize: PendingResize,
+}
+
+/// Statistics about the most recent pump of the winit event loop
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PumpStats {
+    /// Number of window, device and redraw events received during the pump
+    ///
+    /// winit's own bookkeeping events, like the start and end of each event loop iteration, aren't
+    ///  counted.
+    pub events_processed: usize,
+    /// Whether the pump returned because it timed out waiting for events
+    ///
+    /// Reserved for pumps with a timeout. `poll_event` never waits, so this is always `false`.
+    pub timed_out: bool,
+    /// Whether the event loop ran with `Poll` rather than `Wait` control flow
+    ///
+    /// This follows `set_continuous_rendering`.
+    pub polled: bool,
+}
+
+/// Mouse cursor icons that can be shown over the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorIcon {
+    /// The platform-dependent default cursor, usually an arrow
+    Arrow,
+    /// Indicates text that may be selected or edited
+    Text,
+    /// Indicates vertical text that may be selected or edited
+    VerticalText,
+    /// Indicates a precise selection
+    Crosshair,
+    /// Indicates a link or something clickable
+    Hand,
+    /// Indicates help is available
+    Help,
+    /// Indicates the program is busy
+    Wait,
+    /// Indicates the program is busy, but can still be interacted with
+    Progress,
+    /// Indicates the requested action can't be performed
+    NotAllowed,
+    /// Indicates a context menu is available
+    ContextMenu,
+    /// Indicates a cell or set of cells may be selected
+    Cell,
+    /// Indicates an alias or shortcut is to be created
+    Alias,
+    /// Indicates something is to be copied
+    Copy,
+    /// Indicates something can't be dropped here
+    NoDrop,
+    /// Indicates something can be moved
+    Move,
+    /// Indicates something can be grabbed
+    Grab,
+    /// Indicates something is being grabbed
+    Grabbing,
+    /// Indicates something can be scrolled in any direction
+    AllScroll,
+    /// Indicates something can be zoomed in
+    ZoomIn,
+    /// Indicates something can be zoomed out
+    ZoomOut,
+    /// Indicates the east edge is to be moved
+    EResize,
+    /// Indicates the north edge is to be moved
+    NResize,
+    /// Indicates the north-east corner is to be moved
+    NeResize,
+    /// Indicates the north-west corner is to be moved
+    NwResize,
+    /// Indicates the south edge is to be moved
+    SResize,
+    /// Indicates the south-east corner is to be moved
+    SeResize,
+    /// Indicates the south-west corner is to be moved
+    SwResize,
+    /// Indicates the west edge is to be moved
+    WResize,
+    /// Indicates a horizontal (east-west) resize
+    EwResize,
+    /// Indicates a vertical (north-south) resize
+    NsResize,
+    /// Indicates a diagonal resize from the north-east or south-west corner
+    NeswResize,
+    /// Indicates a diagonal resize from the north-west or south-east corner
+    NwseResize,
+    /// Indicates a column can be resized horizontally
+    ColResize,
+    /// Indicates a row can be resized vertically
+    RowResize,
+}
+
+/// Event id for the custom event emitted when the window moves to a different monitor.
+///
+/// The event's arguments are the new monitor's [`MonitorInfo`].
+pub const MONITOR_CHANGED: EventId = EventId("winit_window/monitor_changed");
+
+/// Event id for the custom event emitted when the system switches between light and dark mode.
+///
+/// The event's arguments are the new [`Theme`].
+pub const THEME_CHANGED: EventId = EventId("winit_window/theme_changed");
+
+/// Light or dark appearance of the system, see [`WinitWindow::theme`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Theme {
+    /// Dark text on a light background
+    Light,
+    /// Light text on a dark background
+    Dark,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::Light
+    }
+}
+
+/// Information about a monitor
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    /// Human-readable name of the monitor, if the platform provides one
+    pub name: Option<String>,
+    /// Top-left corner of the monitor on the desktop, in physical pixels
+    pub position: Position,
+    /// Resolution of the monitor in physical pixels
+    pub size: Size,
+    /// Scale factor of the monitor
+    pub scale_factor: f64,
+    /// Refresh rate of the monitor in millihertz, if the platform provides one
+    pub refresh_rate_millihertz: Option<u32>,
+}
+
+/// Summary of the window attributes as currently applied
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowAttributesSummary {
+    /// Title of the window
+    pub title: String,
+    /// Size of the window's client area in logical pixels
+    pub size: Size,
+    /// Outer position of the window in physical pixels, if the platform provides one
+    pub position: Option<Position>,
+    /// Whether the user can resize the window
+    pub resizable: bool,
+    /// Whether the window has decorations
+    pub decorations: bool,
+    /// Whether the window is fullscreen
+    pub fullscreen: bool,
+    /// Whether the window is visible, if the platform provides it
+    pub visible: Option<bool>,
+}
+
+/// State of the modifier keys
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Modifiers {
+    /// Either shift key is held
+    pub shift: bool,
+    /// Either control key is held
+    pub ctrl: bool,
+    /// Either alt key is held
+    pub alt: bool,
+    /// Either logo key (Windows, Command or Super) is held
+    pub logo: bool,
+}
+
+/// Consolidated input state for a frame, see [`WinitWindow::input_snapshot`]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct InputSnapshot {
+    /// Last known cursor position, in the same coordinates as `Motion::MouseCursor`
+    pub cursor: [f64; 2],
+    /// Keyboard and mouse buttons currently held
+    pub pressed: Vec<Button>,
+    /// Scroll accumulated this frame
+    pub scroll: [f64; 2],
+    /// Text typed this frame
+    pub text: String,
+    /// Current modifier key state
+    pub modifiers: Modifiers,
+}
+
+/// A touch currently on the screen, see [`WinitWindow::active_touches`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchInfo {
+    /// Identifier of the touch device, as in `TouchArgs::device`
+    pub device: i64,
+    /// Identifier of the touch, as in `TouchArgs::id`
+    pub id: i64,
+    /// Last position of the touch, normalized to the draw area
+    pub position: [f64; 2],
+    /// Last pressure of the touch, normalized to `0.0..=1.0`
+    pub pressure: f64,
+}
+
+/// Kinds of text input the input method can adapt to, see [`WinitWindow::set_ime_purpose`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImePurpose {
+    /// Regular text
+    Normal,
+    /// A password, which shouldn't be suggested or remembered
+    Password,
+    /// Input into a terminal
+    Terminal,
+}
+
+impl Default for ImePurpose {
+    fn default() -> Self {
+        ImePurpose::Normal
+    }
+}
+
+/// Stacking of the window relative to other windows, see [`WinitWindow::set_window_level`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowLevel {
+    /// Stacked like any other window
+    Normal,
+    /// Always above normal windows
+    AlwaysOnTop,
+    /// Always below normal windows
+    AlwaysOnBottom,
+}
+
+impl Default for WindowLevel {
+    fn default() -> Self {
+        WindowLevel::Normal
+    }
+}
+
+/// How the cursor is held while captured, see [`WinitWindow::set_cursor_grab_mode`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorGrab {
+    /// The cursor isn't grabbed, it's only hidden and warped back to the capture center
+    None,
+    /// The cursor is kept inside the window, and warped back to the capture center
+    Confined,
+    /// The cursor is locked in place
+    Locked,
+}
+
+impl Default for CursorGrab {
+    fn default() -> Self {
+        CursorGrab::Locked
+    }
+}
+
+/// Kinds of fullscreen a window can be in, see [`WinitWindow::fullscreen_mode`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FullscreenKind {
+    /// A borderless window covering the monitor
+    Borderless,
+    /// Exclusive fullscreen with a video mode change
+    Exclusive,
+}
+
+/// Optional window capabilities that depend on the platform, see [`WinitWindow::supports`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowFeature {
+    /// Hiding the window contents from screen capture
+    ContentProtection,
+    /// Letting cursor events pass through the window to whatever is behind it
+    CursorHittest,
+    /// Grabbing the cursor with `CursorGrabMode::Locked`
+    CursorLock,
+    /// Grabbing the cursor with `CursorGrabMode::Confined`
+    CursorConfine,
 }
 
 /// Custom events for the winit event loop
@@ -54,294 +446,1667 @@ pub enum UserEvent {
 }
 
 impl WinitWindow {
+    /// Creates a new window, panicking if the window could not be created.
+    ///
+    /// See [`WinitWindow::try_new`] for a non-panicking version.
     pub fn new(settings: &WindowSettings) -> Self {
+        Self::try_new(settings).expect("Failed to create window")
+    }
+
+    /// Creates a new window, returning an error if the window could not be created.
+    ///
+    /// Window creation can fail at runtime, for example because of GPU driver issues or a
+    ///  disconnected display. It also fails when called from a thread other than the main thread,
+    ///  since winit requires the event loop to live there.
+    pub fn try_new(settings: &WindowSettings) -> Result<Self, Box<dyn Error>> {
+        Self::try_new_with_builder(settings, |builder| builder)
+    }
+
+    /// Creates a new window, letting `f` customize the winit [`WindowBuilder`] first.
+    ///
+    /// This is an escape hatch for window attributes this crate doesn't expose yet. `f` runs once,
+    ///  after the builder has been configured from `settings` and right before the window is
+    ///  created, so anything it sets takes precedence over `settings`.
+    pub fn try_new_with_builder<F>(settings: &WindowSettings, f: F) -> Result<Self, Box<dyn Error>>
+    where
+        F: FnOnce(WindowBuilder) -> WindowBuilder,
+    {
         use winit::event_loop::EventLoopBuilder;
-        let event_loop = EventLoopBuilder::with_user_event().build();
-        let window = WindowBuilder::new()
+
+        // winit panics rather than returning an error when the event loop can't be created, most
+        //  commonly because we're not on the main thread, so turn that into an error instead
+        let event_loop = panic::catch_unwind(|| EventLoopBuilder::with_user_event().build())
+            .map_err(|payload| {
+                let reason = payload
+                    .downcast_ref::<&str>()
+                    .map(|reason| reason.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown error".to_string());
+                format!(
+                    "Failed to create the winit event loop, note that it must be created on the \
+                     main thread: {}",
+                    reason
+                )
+            })?;
+        let builder = WindowBuilder::new()
             .with_inner_size(LogicalSize::<f64>::new(
-                settings.get_size().width.into(),
-                settings.get_size().height.into(),
+                settings.get_size().width,
+                settings.get_size().height,
             ))
             .with_title(settings.get_title())
-            .build(&event_loop)
-            .unwrap();
+            .with_resizable(settings.get_resizable())
+            .with_decorations(settings.get_decorated())
+            .with_transparent(settings.get_transparent())
+            .with_fullscreen(if settings.get_fullscreen() {
+                Some(Fullscreen::Borderless(None))
+            } else {
+                None
+            });
+        let builder = f(builder);
+        let title = builder.window_attributes().title.clone();
+        let resizable = builder.window_attributes().resizable;
+        let decorations = builder.window_attributes().decorations;
+        let transparent = builder.transparent();
+        let window = builder.build(&event_loop)?;
+        let window_cache = WindowCache::new(window.inner_size());
+        let x11 = x11::X11::new(&window);
 
-        WinitWindow {
-            window,
+        Ok(WinitWindow {
             event_loop,
 
             should_close: false,
-            queued_events: VecDeque::new(),
+            shut_down: false,
+            pumping_enabled: true,
+            window_cache,
+            queue: EventQueue::new(),
             last_cursor: LogicalPosition::new(0.0, 0.0),
             cursor_accumulator: LogicalPosition::new(0.0, 0.0),
+            cursor_inside: false,
+            cursor_physical: PhysicalPosition::new(0.0, 0.0),
+            ime_preedit: String::new(),
+            ime_purpose: ImePurpose::Normal,
+            modifiers: ModifiersState::empty(),
+            modifier_keys_seen: ModifiersState::empty(),
+            modifiers_changed: false,
 
-            title: settings.get_title(),
+            title,
+            app_name: String::new(),
             capture_cursor: false,
+            capture_center: [0.5, 0.5],
+            pre_capture_cursor: None,
+            skip_capture_warp: false,
+            focus_on_capture: false,
+            cursor_grab_mode: CursorGrab::None,
+            capture_grab: CursorGrab::Locked,
             exit_on_esc: settings.get_exit_on_esc(),
-        }
+            input_options: InputOptions::default(),
+            vsync: settings.get_vsync(),
+            vsync_fallback_limiter: false,
+            last_swap: None,
+            opacity: 1.0,
+            progress: None,
+            visible_on_all_workspaces: false,
+            keep_awake: false,
+            last_awake_reset: None,
+            x11,
+            hdr_requested: false,
+            occluded: false,
+            continuous_rendering: false,
+            render_events: false,
+            last_render: None,
+            key_repeat: KeyRepeat::default(),
+            suppress_button_for_text: false,
+            text_keys: TextKeys::default(),
+            monitor: window.current_monitor(),
+            scale_factor: window.scale_factor(),
+            theme: window.theme().map_or(Theme::Light, map_theme),
+            monitor_dirty: false,
+            last_moved: None,
+            requested_position: None,
+            windowed_size: LogicalSize::new(settings.get_size().width, settings.get_size().height)
+                .into(),
+            resizable,
+            resizable_locked: false,
+            decorations,
+            transparent,
+            blur: false,
+            dropped_files: Vec::new(),
+            file_drop_handler: None,
+            window,
+
+            last_error: None,
+            last_pump_stats: PumpStats::default(),
+            resize_debounce: Duration::ZERO,
+            last_resize: None,
+            pending_resize: PendingResize::default(),
+        })
     }
 
     pub fn get_window(&self) -> &winit::window::Window {
         &self.window
     }
 
-    fn handle_event<T>(&mut self, event: winit::event::Event<T>, center: PhysicalPosition<f64>) {
-        match event {
-            winit::event::Event::WindowEvent { event, .. } => {
-                // Special event handling.
-                // Some events are not exposed to user and handled internally.
-                match event {
-                    WindowEvent::KeyboardInput { input, .. } => {
-                        if self.exit_on_esc {
-                            if let Some(VirtualKeyCode::Escape) = input.virtual_keycode {
-                                self.set_should_close(true);
-                                return;
-                            }
-                        }
-                    }
-                    WindowEvent::CursorMoved { position, .. } => {
-                        if self.capture_cursor {
-                            let prev_last_cursor = self.last_cursor;
-                            self.last_cursor =
-                                position.to_logical(self.get_window().scale_factor());
-
-                            // Don't track distance if the position is at the center, this probably is
-                            //  from cursor center lock, or irrelevant.
-                            if position == center {
-                                return;
-                            }
-
-                            // Add the distance to the tracked cursor movement
-                            self.cursor_accumulator.x += position.x - prev_last_cursor.x as f64;
-                            self.cursor_accumulator.y += position.y - prev_last_cursor.y as f64;
-
-                            return;
-                        }
-                    }
-                    _ => {}
-                }
+    /// Returns the window, unless the platform destroyed it and winit can't be called on it.
+    fn live_window(&self) -> Option<&winit::window::Window> {
+        if self.window_cache.destroyed {
+            None
+        } else {
+            Some(&self.window)
+        }
+    }
 
-                // Usual events are handled here and passed to user.
-                if let Some(ev) = map_window_event(event) {
-                    self.queued_events.push_back(ev);
-                }
+    /// Returns the size of the window's client area in physical pixels, or the last known size
+    ///  once the window is destroyed.
+    fn inner_size(&self) -> PhysicalSize<u32> {
+        self.live_window()
+            .map_or(self.window_cache.inner_size, |window| window.inner_size())
+    }
+
+    /// Returns the winit event loop, for platform-specific configuration.
+    ///
+    /// The window keeps owning the event loop for its whole lifetime, `poll_event` pumps it with
+    ///  `run_return` rather than consuming it. Note that the control flow is decided inside each
+    ///  pump, so changes made to it from outside don't carry over.
+    pub fn event_loop_mut(&mut self) -> &mut EventLoop<UserEvent> {
+        &mut self.event_loop
+    }
+
+    /// Sets the application name used by `set_document_title`.
+    pub fn set_app_name(&mut self, name: String) {
+        self.app_name = name;
+    }
+
+    /// Sets the title to `"<doc> — <app name>"`, or just `doc` when no app name is set.
+    ///
+    /// Use `set_title` for full control over the title.
+    pub fn set_document_title(&mut self, doc: &str) {
+        let title = if self.app_name.is_empty() {
+            doc.to_string()
+        } else {
+            format!("{} — {}", doc, self.app_name)
+        };
+        self.set_title(title);
+    }
+
+    /// Sets the window icon, shown in the title bar and taskbar, from RGBA pixels.
+    ///
+    /// `rgba` holds 4 bytes per pixel, row by row. An error is returned without changing the
+    ///  icon when its length doesn't match `width` and `height`.
+    pub fn set_icon(&mut self, rgba: Vec<u8>, width: u32, height: u32) -> Result<(), BadIcon> {
+        check_icon_size(&rgba, width, height)?;
+        let icon = Icon::from_rgba(rgba, width, height)?;
+        if let Some(window) = self.live_window() {
+            window.set_window_icon(Some(icon));
+        }
+        Ok(())
+    }
+
+    /// Changes the mouse cursor icon shown over the window.
+    pub fn set_cursor_icon(&mut self, cursor: CursorIcon) {
+        if let Some(window) = self.live_window() {
+            window.set_cursor_icon(map_cursor(cursor));
+        }
+    }
+
+    /// Changes the mouse cursor icon, the same as `set_cursor_icon`.
+    pub fn set_cursor(&mut self, cursor: CursorIcon) {
+        self.set_cursor_icon(cursor);
+    }
+
+    /// Returns how many times the most recently pressed key has repeated while held.
+    ///
+    /// This is `0` for the initial press, and resets when the key is released or a different key
+    ///  is pressed. The repeated presses are still emitted as button events.
+    pub fn key_repeat_count(&self) -> u32 {
+        self.key_repeat.count
+    }
+
+    /// Returns the cursor grab mode currently in effect.
+    ///
+    /// This is the mode that was actually applied, which lets applications tell the user when
+    ///  relative mouse input isn't available.
+    pub fn active_cursor_grab_mode(&self) -> CursorGrab {
+        self.cursor_grab_mode
+    }
+
+    /// Sets how the cursor is grabbed while captured.
+    ///
+    /// Relative motion is reported the same way whatever the mode. Locking, the default, isn't
+    ///  supported everywhere. Confining still works on more platforms, relying on the warp to
+    ///  the capture center every frame. If the cursor is captured already, the new mode is
+    ///  applied right away.
+    pub fn set_cursor_grab_mode(&mut self, mode: CursorGrab) {
+        self.capture_grab = mode;
+        if let (true, Some(window)) = (self.capture_cursor, self.live_window()) {
+            let result = window.set_cursor_grab(map_cursor_grab(mode));
+            if result.is_ok() {
+                self.cursor_grab_mode = mode;
             }
-            _ => (),
+            self.record_error(result);
         }
     }
-}
 
-impl Window for WinitWindow {
-    fn set_should_close(&mut self, value: bool) {
-        self.should_close = value;
+    /// Captures or releases the cursor, like `set_capture_cursor`, but reports grab failures.
+    ///
+    /// Nothing is changed when the cursor grab fails. `set_capture_cursor` instead falls back to
+    ///  the less strict grab modes.
+    pub fn try_set_capture_cursor(&mut self, value: bool) -> Result<(), Box<dyn Error>> {
+        // If we're already doing this, just don't do anything
+        if value == self.capture_cursor {
+            return Ok(());
+        }
+
+        let window = self
+            .live_window()
+            .ok_or("The window was destroyed by the platform")?;
+        if value {
+            if self.focus_on_capture {
+                window.focus_window();
+            }
+            window.set_cursor_grab(map_cursor_grab(self.capture_grab))?;
+            self.start_capture(self.capture_grab);
+        } else {
+            window.set_cursor_grab(CursorGrabMode::None)?;
+            window.set_cursor_visible(true);
+            self.cursor_grab_mode = CursorGrab::None;
+            self.capture_cursor = false;
+
+            // Put the cursor back where it was, rather than leaving it at the capture center
+            if let Some(position) = self.pre_capture_cursor.take() {
+                let result = self.get_window().set_cursor_position(position);
+                self.record_error(result);
+            }
+        }
+        Ok(())
     }
 
-    fn should_close(&self) -> bool {
-        self.should_close
+    /// Hides the cursor and starts tracking relative motion, with `mode` already applied.
+    fn start_capture(&mut self, mode: CursorGrab) {
+        self.window.set_cursor_visible(false);
+        self.cursor_grab_mode = mode;
+        self.cursor_accumulator = LogicalPosition::new(0.0, 0.0);
+        self.last_cursor = self.cursor_physical.to_logical(self.scale_factor);
+        self.pre_capture_cursor = if self.cursor_inside {
+            Some(self.cursor_physical)
+        } else {
+            None
+        };
+        // Warping right away makes quick capture toggles jump, wait for the next frame
+        self.skip_capture_warp = true;
+        self.capture_cursor = true;
     }
 
-    fn size(&self) -> Size {
-        let (w, h): (u32, u32) = self.get_window().inner_size().into();
-        let hidpi = self.get_window().scale_factor();
-        ((w as f64 / hidpi) as u32, (h as f64 / hidpi) as u32).into()
+    /// Sets whether capturing the cursor also focuses the window.
+    ///
+    /// Without focus, captured input goes nowhere. Off by default.
+    pub fn set_focus_on_capture(&mut self, value: bool) {
+        self.focus_on_capture = value;
     }
 
-    fn swap_buffers(&mut self) {
-        // This window backend was made for use with a vulkan renderer that handles swapping by
-        //  itself, if you need it here open up an issue. What we can use this for however is
-        //  detecting the end of a frame, which we can use to gather up cursor_accumulator data.
+    /// Sets whether mouse buttons without a Piston equivalent are dropped.
+    ///
+    /// By default they're reported as `MouseButton::Unknown`.
+    pub fn set_ignore_unknown_mouse_buttons(&mut self, value: bool) {
+        self.input_options.ignore_unknown_mouse_buttons = value;
+    }
 
-        if self.capture_cursor {
-            let center: (f64, f64) = self.get_window().inner_size().into();
-            let mut center: PhysicalPosition<f64> = center.into();
-            center.x /= 2.;
-            center.y /= 2.;
+    /// Clears transient input state, so stale input doesn't leak across a scene switch or pause.
+    ///
+    /// This forgets held buttons, active touches, the held key used for repeat counting and any
+    ///  accumulated relative cursor motion. When `clear_queued` is set, queued input events are
+    ///  dropped as well, though window events like close, resize and focus are kept. Settings like
+    ///  cursor capture are left alone.
+    pub fn reset_input_state(&mut self, clear_queued: bool) {
+        self.queue.pressed_buttons.clear();
+        self.queue.active_touches.clear();
+        self.key_repeat = KeyRepeat::default();
+        self.cursor_accumulator = LogicalPosition::new(0.0, 0.0);
+        if clear_queued {
+            self.queue.events.retain(|event| !is_user_input(event));
+        }
+    }
 
-            // Center-lock the cursor if we're using capture_cursor
-            self.get_window().set_cursor_position(center).unwrap();
+    /// Sets whether resize events report the window size in physical pixels.
+    ///
+    /// By default `ResizeArgs::window_size` is in logical points and `ResizeArgs::draw_size` is in
+    ///  physical pixels. Pixel-perfect renderers can turn this on to get physical pixels in both.
+    pub fn set_resize_in_physical(&mut self, value: bool) {
+        self.input_options.resize_in_physical = value;
+    }
 
-            // Create a relative input based on the distance from the center
-            self.queued_events.push_back(Event::Input(
-                Input::Move(Motion::MouseRelative([
-                    self.cursor_accumulator.x,
-                    self.cursor_accumulator.y,
-                ])),
-                None,
-            ));
+    /// Returns whether the cursor is over the window.
+    ///
+    /// This is `false` until the first cursor enter event arrives.
+    pub fn cursor_inside(&self) -> bool {
+        self.cursor_inside
+    }
 
-            self.cursor_accumulator = LogicalPosition::new(0.0, 0.0);
+    /// Returns a snapshot of the current input state.
+    ///
+    /// This saves immediate-mode UI integrations from aggregating raw events themselves. The
+    ///  scroll and text are what arrived since the last `swap_buffers`, which marks the end of a
+    ///  frame.
+    pub fn input_snapshot(&self) -> InputSnapshot {
+        InputSnapshot {
+            cursor: self.queue.cursor_position,
+            pressed: self.queue.pressed_buttons.iter().cloned().collect(),
+            scroll: self.queue.frame_scroll,
+            text: self.queue.frame_text.clone(),
+            modifiers: Modifiers {
+                shift: self.modifiers.shift(),
+                ctrl: self.modifiers.ctrl(),
+                alt: self.modifiers.alt(),
+                logo: self.modifiers.logo(),
+            },
         }
     }
 
-    fn wait_event(&mut self) -> Event {
-        // TODO: Implement this
-        unimplemented!()
+    /// Returns the touches currently on the screen, ordered by device and touch id.
+    ///
+    /// Touches are added when they start and removed when they end or are cancelled.
+    pub fn active_touches(&self) -> Vec<TouchInfo> {
+        self.queue.active_touches.values().cloned().collect()
     }
 
-    fn wait_event_timeout(&mut self, _timeout: Duration) -> Option<Event> {
-        // TODO: Implement this
-        unimplemented!()
+    /// Sets the smallest scroll delta that produces an event.
+    ///
+    /// Scroll events whose delta has a magnitude below `dead_zone` are dropped, filtering out the
+    ///  jitter trackpads can produce at rest. Defaults to `0.0`, which lets everything through.
+    pub fn set_scroll_dead_zone(&mut self, dead_zone: f64) {
+        self.input_options.scroll_dead_zone = dead_zone;
     }
 
-    fn poll_event(&mut self) -> Option<Event> {
-        let center: (f64, f64) = self.get_window().inner_size().into();
-        let mut center: PhysicalPosition<f64> = center.into();
-        center.x /= 2.;
-        center.y /= 2.;
+    /// Sets whether key presses that produce text are reported only as text.
+    ///
+    /// When on, a printable key yields just an `Input::Text` event, without the keyboard button
+    ///  press and release, so applications don't handle the keystroke twice. Keys that don't
+    ///  produce text, like arrows, still produce button events. Off by default.
+    pub fn set_suppress_button_for_text(&mut self, value: bool) {
+        self.suppress_button_for_text = value;
+    }
 
-        // Add all events we got to the event queue, since winit only allows us to get all pending
-        //  events at once.
-        {
-            let mut events: Vec<winit::event::Event<UserEvent>> = Vec::new();
-            let event_loop_proxy = self.event_loop.create_proxy();
-            event_loop_proxy
-                .send_event(UserEvent::WakeUp)
-                .expect("Event loop is closed before property handling all events.");
+    /// Delivers the remaining events and shuts the window down.
+    ///
+    /// This pumps the event loop one last time and returns every event still queued, including
+    ///  any resize held back by debouncing, so the application can process them before tearing
+    ///  down. Afterwards `should_close` is `true` and the event loop is no longer pumped.
+    pub fn shutdown(&mut self) -> Vec<Event> {
+        self.pump_events();
+        self.shut_down = true;
+        self.set_should_close(true);
 
-            self.event_loop.run_return(|event, _, control_flow| {
-                if let Some(e) = event.to_static() {
-                    if e == winit::event::Event::UserEvent(UserEvent::WakeUp) {
-                        *control_flow = ControlFlow::Exit;
-                        return;
-                    }
-                    events.push(e);
-                }
-            });
-            for event in events.into_iter() {
-                self.handle_event(event, center)
-            }
+        if let Some(resize) = self.pending_resize.take() {
+            self.queue.events.push_back(resize);
         }
+        self.queue.events.drain(..).collect()
+    }
 
-        // Get the first event in the queue
-        let event = self.queued_events.pop_front();
+    /// Enables or disables input events.
+    ///
+    /// While disabled, keyboard, mouse and text input is dropped instead of being queued, which
+    ///  is useful during modal operations like native dialogs or cutscenes. Window events like
+    ///  close, resize and focus are still delivered.
+    pub fn set_input_enabled(&mut self, enabled: bool) {
+        self.input_options.input_enabled = enabled;
+    }
 
-        // Check if we got a close event, if we did we need to mark ourselves as should-close
-        if let &Some(Event::Input(Input::Close(_), ..)) = &event {
-            self.set_should_close(true);
+    /// Sets extra scancode to key mappings, used for keys the crate would report as
+    ///  `Key::Unknown`.
+    ///
+    /// This is a stopgap for keyboard layouts the crate doesn't cover, such as keys for `ß`, `ç`
+    ///  or `µ`. Scancodes are platform-specific, they're the same values reported in
+    ///  `ButtonArgs::scancode`.
+    pub fn set_extra_key_map(&mut self, map: HashMap<i32, Key>) {
+        self.input_options.extra_key_map = map;
+    }
+
+    /// Returns the key at the physical position of `scancode`, whatever character it types.
+    ///
+    /// This lets rebinding UIs label a key by where it is, like the key where `W` is on a US
+    ///  layout. Keys from `set_extra_key_map` take precedence. Returns `Key::Unknown` for media
+    ///  and other uncommon keys, and for every key on macOS.
+    pub fn key_from_scancode(&self, scancode: i32) -> Key {
+        match self.input_options.extra_key_map.get(&scancode) {
+            Some(&key) => key,
+            None => map_scancode(scancode),
         }
+    }
 
-        event
+    /// Sets whether keyboard button events report SDL2 scancodes instead of platform ones.
+    ///
+    /// Platform scancodes differ between operating systems, SDL2 scancodes are the same everywhere
+    ///  and match what other Piston back-ends report. Keys without an SDL2 scancode in the
+    ///  table, and every key on macOS, report no scancode. Scancodes given to
+    ///  `set_extra_key_map` and `key_from_scancode` are still platform ones. Defaults to `false`.
+    pub fn set_sdl_scancodes(&mut self, value: bool) {
+        self.input_options.sdl_scancodes = value;
     }
 
-    fn draw_size(&self) -> Size {
-        let size: (f64, f64) = self.get_window().inner_size().into();
-        size.into()
+    /// Records whether the application would like HDR output.
+    ///
+    /// Renderers can query it with `hdr_requested` when picking a surface format and color space.
+    pub fn set_hdr_requested(&mut self, hdr: bool) {
+        self.hdr_requested = hdr;
     }
-}
 
-impl AdvancedWindow for WinitWindow {
-    fn get_title(&self) -> String {
-        self.title.clone()
+    /// Returns whether the application requested HDR output.
+    pub fn hdr_requested(&self) -> bool {
+        self.hdr_requested
     }
 
-    fn set_title(&mut self, value: String) {
-        self.get_window().set_title(&value);
-        self.title = value;
+    /// Returns whether the window settings asked for vsync.
+    ///
+    /// Presenting frames is up to the renderer, which should honor this when configuring its
+    ///  surface.
+    pub fn vsync(&self) -> bool {
+        self.vsync
     }
 
-    fn get_exit_on_esc(&self) -> bool {
-        self.exit_on_esc
+    /// Sets whether to limit the frame rate when vsync was requested but doesn't take effect.
+    ///
+    /// Some drivers ignore vsync, letting applications spin at thousands of frames per second.
+    ///  With this on, `swap_buffers` sleeps for the rest of the monitor's refresh interval when a
+    ///  frame took less than half of it. This does nothing unless the window settings asked for
+    ///  vsync. Defaults to `false`.
+    pub fn set_vsync_fallback_limiter(&mut self, value: bool) {
+        self.vsync_fallback_limiter = value;
     }
 
-    fn set_exit_on_esc(&mut self, value: bool) {
-        self.exit_on_esc = value
+    /// Sets whether to render continuously, which suits games, rather than only when needed.
+    ///
+    /// When continuous, a redraw is requested on every pump of the event loop, so a render event
+    ///  follows even without any input. Otherwise render events only come when the platform asks
+    ///  for a redraw, which suits GUI applications. Defaults to `false`.
+    ///
+    /// Render events are only emitted with `set_render_events` on.
+    pub fn set_continuous_rendering(&mut self, continuous: bool) {
+        self.continuous_rendering = continuous;
     }
 
-    fn set_capture_cursor(&mut self, value: bool) {
-        // If we're already doing this, just don't do anything
-        if value == self.capture_cursor {
+    /// Sets whether the platform's redraw requests are emitted as render events.
+    ///
+    /// Piston's event loop already emits its own render events, so leave this off when using it,
+    ///  or it renders more often than asked. Defaults to `false`.
+    pub fn set_render_events(&mut self, value: bool) {
+        self.render_events = value;
+    }
+
+    /// Switches between borderless fullscreen on the current monitor and windowed mode.
+    ///
+    /// Going back to windowed mode restores the size the window had before going fullscreen, or
+    ///  the size it was created with when it started out fullscreen.
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        if self.window_cache.destroyed {
             return;
         }
+        let was_fullscreen = self.window.fullscreen().is_some();
+        if fullscreen {
+            if !was_fullscreen {
+                self.windowed_size = self.window.inner_size().into();
+            }
+            self.window
+                .set_fullscreen(Some(Fullscreen::Borderless(None)));
+        } else if was_fullscreen {
+            self.window.set_fullscreen(None);
+            self.window.set_inner_size(self.windowed_size);
+        }
+    }
 
-        let window = self.get_window();
-        if value {
-            window.set_cursor_grab(CursorGrabMode::Locked).unwrap();
-            window.set_cursor_visible(false);
-            self.cursor_accumulator = LogicalPosition::new(0.0, 0.0);
-            let mut center = self.get_window().inner_size().cast::<f64>();
-            center.width /= 2.;
-            center.height /= 2.;
-            self.last_cursor = LogicalPosition::new(center.width, center.height);
-        } else {
-            window.set_cursor_grab(CursorGrabMode::None).unwrap();
-            window.set_cursor_visible(true);
+    /// Returns which kind of fullscreen the window is in, if any.
+    pub fn fullscreen_mode(&self) -> Option<FullscreenKind> {
+        self.live_window()?
+            .fullscreen()
+            .map(|fullscreen| match fullscreen {
+                Fullscreen::Borderless(_) => FullscreenKind::Borderless,
+                Fullscreen::Exclusive(_) => FullscreenKind::Exclusive,
+            })
+    }
+
+    /// Sets whether the user can resize the window.
+    ///
+    /// While `set_resizable_lock` holds the window, this only takes effect once unlocked.
+    pub fn set_resizable(&mut self, resizable: bool) {
+        self.resizable = resizable;
+        if let (false, Some(window)) = (self.resizable_locked, self.live_window()) {
+            window.set_resizable(resizable);
         }
-        self.capture_cursor = value;
     }
 
-    fn get_automatic_close(&self) -> bool {
-        false
+    /// Returns whether the window was asked to be resizable, from the window settings or
+    ///  `set_resizable`.
+    pub fn get_resizable(&self) -> bool {
+        self.resizable
     }
 
-    fn set_automatic_close(&mut self, _value: bool) {
-        // TODO: Implement this
+    /// Sets whether the window stays above or below other windows.
+    pub fn set_window_level(&mut self, level: WindowLevel) {
+        if let Some(window) = self.live_window() {
+            window.set_window_level(map_window_level(level));
+        }
     }
 
-    fn show(&mut self) {
-        self.get_window().set_visible(true);
+    /// Maximizes or restores the window.
+    pub fn set_maximized(&mut self, maximized: bool) {
+        if let Some(window) = self.live_window() {
+            window.set_maximized(maximized);
+        }
     }
 
-    fn hide(&mut self) {
-        self.get_window().set_visible(false);
+    /// Returns whether the window is maximized.
+    pub fn is_maximized(&self) -> bool {
+        self.live_window()
+            .map_or(false, |window| window.is_maximized())
     }
 
-    fn get_position(&self) -> Option<Position> {
-        self.get_window()
-            .outer_position()
-            .map(|p| Position { x: p.x, y: p.y })
-            .ok()
+    /// Minimizes or restores the window.
+    ///
+    /// Render events stop while minimized on platforms that report the window as occluded.
+    pub fn set_minimized(&mut self, minimized: bool) {
+        if let Some(window) = self.live_window() {
+            window.set_minimized(minimized);
+        }
     }
 
-    fn set_position<P: Into<Position>>(&mut self, val: P) {
-        let val = val.into();
-        self.get_window()
-            .set_outer_position(LogicalPosition::new(val.x as f64, val.y as f64))
+    /// Sets the smallest size the window's client area can be resized to, in logical pixels.
+    ///
+    /// `None` removes the constraint.
+    pub fn set_min_size(&mut self, size: Option<Size>) {
+        if let Some(window) = self.live_window() {
+            window.set_min_inner_size(size.map(|size| LogicalSize::new(size.width, size.height)));
+        }
     }
 
-    fn set_size<S: Into<Size>>(&mut self, size: S) {
-        let size: Size = size.into();
-        let hidpi = self.get_window().scale_factor();
-        self.get_window().set_inner_size(LogicalSize::new(
-            size.width as f64 * hidpi,
-            size.height as f64 * hidpi,
-        ));
+    /// Sets the largest size the window's client area can be resized to, in logical pixels.
+    ///
+    /// `None` removes the constraint.
+    pub fn set_max_size(&mut self, size: Option<Size>) {
+        if let Some(window) = self.live_window() {
+            window.set_max_inner_size(size.map(|size| LogicalSize::new(size.width, size.height)));
+        }
     }
-}
 
-#[cfg(not(feature = "use-vulkano"))]
-impl BuildFromWindowSettings for WinitWindow {
-    fn build_from_window_settings(settings: &WindowSettings) -> Result<Self, Box<dyn Error>> {
-        Ok(Self::new(settings))
+    /// Sets whether the window has decorations, like a title bar and borders.
+    pub fn set_decorations(&mut self, decorations: bool) {
+        self.decorations = decorations;
+        if let Some(window) = self.live_window() {
+            window.set_decorations(decorations);
+        }
     }
-}
 
-fn map_key(input: &KeyboardInput) -> Key {
-    use winit::event::VirtualKeyCode::*;
-    // TODO: Complete the lookup match
-    if let Some(vk) = input.virtual_keycode {
-        match vk {
-            Key1 => Key::D1,
-            Key2 => Key::D2,
-            Key3 => Key::D3,
-            Key4 => Key::D4,
-            Key5 => Key::D5,
-            Key6 => Key::D6,
-            Key7 => Key::D7,
-            Key8 => Key::D8,
-            Key9 => Key::D9,
-            Key0 => Key::D0,
-            A => Key::A,
-            B => Key::B,
-            C => Key::C,
-            D => Key::D,
-            E => Key::E,
-            F => Key::F,
-            G => Key::G,
-            H => Key::H,
-            I => Key::I,
-            J => Key::J,
-            K => Key::K,
-            L => Key::L,
+    /// Returns whether the window was asked to have decorations, from the window settings or
+    ///  `set_decorations`.
+    pub fn get_decorations(&self) -> bool {
+        self.decorations
+    }
+
+    /// Sets whether the window's background is transparent.
+    ///
+    /// Transparency is best set through the window settings, since only macOS and Wayland can
+    ///  change it after the window is created.
+    pub fn set_transparent(&mut self, transparent: bool) {
+        self.transparent = transparent;
+        if let Some(window) = self.live_window() {
+            window.set_transparent(transparent);
+        }
+    }
+
+    /// Returns whether the window was asked to be transparent, from the window settings or
+    ///  `set_transparent`.
+    pub fn get_transparent(&self) -> bool {
+        self.transparent
+    }
+
+    /// Sets whether the compositor blurs what's behind the window, for a frosted-glass look.
+    ///
+    /// This only shows through a transparent window, see `set_transparent`. winit 0.28 can't
+    ///  request blur on any platform, so for now this has no visible effect.
+    pub fn set_blur(&mut self, blur: bool) {
+        self.blur = blur;
+    }
+
+    /// Returns the value last set with `set_blur`.
+    pub fn blur(&self) -> bool {
+        self.blur
+    }
+
+    /// Prevents the user from resizing the window while `locked` is `true`.
+    ///
+    /// The resizable state is restored when unlocking, so applications don't need to remember it
+    ///  across a mode switch.
+    pub fn set_resizable_lock(&mut self, locked: bool) {
+        self.resizable_locked = locked;
+        if let Some(window) = self.live_window() {
+            window.set_resizable(self.resizable && !locked);
+        }
+    }
+
+    /// Calls `handler` once for every completed file drop, with all dropped paths.
+    ///
+    /// winit reports dropped files one at a time, so the files dropped during one pump of the
+    ///  event loop are grouped together. The position is the last known cursor position, since
+    ///  winit doesn't report where the files were dropped. A cancelled drag doesn't call it.
+    pub fn set_file_drop_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(Vec<PathBuf>, Position) + 'static,
+    {
+        self.file_drop_handler = Some(Box::new(handler));
+    }
+
+    /// Swaps the scroll axes while shift is held, so a regular mouse wheel scrolls horizontally.
+    ///
+    /// Defaults to `false`.
+    pub fn set_shift_scrolls_horizontal(&mut self, value: bool) {
+        self.input_options.shift_scrolls_horizontal = value;
+    }
+
+    /// Sets whether Enter, Tab and Backspace also produce text events.
+    ///
+    /// When on, they type `"\n"`, `"\t"` and `"\u{8}"` the same way on every platform. By
+    ///  default control characters never produce text, only button events.
+    pub fn set_control_chars_as_text(&mut self, value: bool) {
+        self.input_options.control_chars_as_text = value;
+    }
+
+    /// Reports input positions with the origin at the bottom of the draw area, like OpenGL.
+    ///
+    /// This flips cursor and touch positions, and the direction of relative mouse motion. Scroll
+    ///  deltas aren't positions and are left as they are.
+    pub fn set_flip_y(&mut self, value: bool) {
+        self.input_options.flip_y = value;
+    }
+
+    /// Holds back resize events until the window size has been stable for `debounce`.
+    ///
+    /// Only the final size of a burst of resizes is emitted, which helps expensive resize
+    ///  handlers. A lone resize is still delivered once `debounce` has passed. Pass
+    ///  `Duration::ZERO`, the default, to emit every resize immediately.
+    pub fn set_resize_debounce(&mut self, debounce: Duration) {
+        self.resize_debounce = debounce;
+    }
+
+    /// Returns the time since the last keyboard, mouse or touch input.
+    ///
+    /// Counts from window creation until the first input arrives.
+    pub fn idle_duration(&self) -> Duration {
+        self.queue.last_input.elapsed()
+    }
+
+    /// Returns the current window size in the same form as resize events.
+    ///
+    /// This lets renderers that start after the last resize set up their viewport. Like resize
+    ///  events, the window size is in logical points unless `set_resize_in_physical` is on.
+    pub fn current_resize_args(&self) -> ResizeArgs {
+        let size = self.inner_size();
+        let scale_factor = if self.input_options.resize_in_physical {
+            1.0
+        } else {
+            self.scale_factor
+        };
+        ResizeArgs {
+            window_size: [
+                size.width as f64 / scale_factor,
+                size.height as f64 / scale_factor,
+            ],
+            draw_size: [size.width, size.height],
+        }
+    }
+
+    /// Returns whether the user is currently resizing the window.
+    ///
+    /// winit has no resize-end signal, so a resize is considered over once no `Resized` event has
+    ///  arrived for the resize debounce duration, or 100 milliseconds when not debouncing.
+    pub fn is_resizing(&self) -> bool {
+        let idle = if self.resize_debounce > Duration::ZERO {
+            self.resize_debounce
+        } else {
+            Duration::from_millis(100)
+        };
+        matches!(self.last_resize, Some(resized_at) if resized_at.elapsed() < idle)
+    }
+
+    /// Pumps the event loop and returns the next queued event, or `None` when there is none.
+    ///
+    /// This is the same as `poll_event`, the name just makes the intent of an `Option`-based
+    ///  event loop clear. It never synthesizes idle events.
+    pub fn next_input(&mut self) -> Option<Event> {
+        self.poll_event()
+    }
+
+    /// Takes the error of the most recent operation that failed, if any.
+    ///
+    /// Operations that can fail on some platforms, like capturing the cursor or warping it back
+    ///  to the capture center, record their error here instead of panicking, so applications can
+    ///  surface platform quirks without every call returning a `Result`.
+    pub fn take_last_error(&mut self) -> Option<String> {
+        self.last_error.take()
+    }
+
+    /// Sets whether `poll_event` pumps the winit event loop for new events.
+    ///
+    /// Disabling this avoids reentrancy during modal native operations like file dialogs. While
+    ///  disabled, already queued events are still returned. Enabled by default.
+    pub fn set_pumping_enabled(&mut self, enabled: bool) {
+        self.pumping_enabled = enabled;
+    }
+
+    /// Returns the number of events queued but not yet returned by `poll_event`.
+    ///
+    /// This doesn't pump the event loop, so events still pending in winit aren't counted.
+    pub fn pending_event_count(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns statistics about the most recent pump of the event loop.
+    ///
+    /// Useful for profiling whether the application is input-bound or idle.
+    pub fn last_pump_stats(&self) -> PumpStats {
+        self.last_pump_stats
+    }
+
+    /// Reports cursor positions relative to a viewport inside the window.
+    ///
+    /// `offset` and `size` are in the same coordinates as `Motion::MouseCursor`. Cursor
+    ///  positions have `offset` subtracted and are clamped to `size`, which is convenient when
+    ///  the rendered area is letterboxed.
+    pub fn set_viewport(&mut self, offset: Position, size: Size) {
+        self.input_options.viewport = Some((offset, size));
+    }
+
+    /// Goes back to reporting cursor positions relative to the whole window.
+    pub fn clear_viewport(&mut self) {
+        self.input_options.viewport = None;
+    }
+
+    /// Scales a logical `base` size by the primary monitor's scale factor.
+    ///
+    /// The result is a physical size that looks right on HiDPI displays. Returns `base` unchanged
+    ///  when no monitor information is available.
+    pub fn suggested_initial_size(&self, base: Size) -> Size {
+        match self.event_loop.primary_monitor() {
+            Some(monitor) => {
+                let scale = monitor.scale_factor();
+                Size {
+                    width: base.width * scale,
+                    height: base.height * scale,
+                }
+            }
+            None => base,
+        }
+    }
+
+    /// Returns whether the system uses a light or dark appearance.
+    ///
+    /// A [`THEME_CHANGED`] event is emitted when this changes. Platforms that don't report a
+    ///  theme are considered light.
+    pub fn theme(&self) -> Theme {
+        self.theme
+    }
+
+    /// Returns whether the window is hidden from view, by other windows or being minimized.
+    ///
+    /// Render events stop while occluded, so games can use this to throttle. Not every platform
+    ///  reports occlusion, in which case this stays `false`.
+    pub fn is_occluded(&self) -> bool {
+        self.occluded
+    }
+
+    /// Returns whether the platform destroyed the window without it being asked to close.
+    ///
+    /// This can happen when a display is unplugged. A close event is emitted when it happens and
+    ///  the error is available from `take_last_error`. The window can't be used anymore, calls
+    ///  on it do nothing and sizes are the last known ones.
+    pub fn is_destroyed(&self) -> bool {
+        self.window_cache.destroyed
+    }
+
+    /// Returns the exact size of the window's client area in logical pixels.
+    ///
+    /// Unlike `size`, this isn't rounded to whole pixels, which matters at fractional scale
+    ///  factors.
+    pub fn logical_size(&self) -> (f64, f64) {
+        self.inner_size()
+            .to_logical::<f64>(self.scale_factor)
+            .into()
+    }
+
+    /// Returns the outer size of the window, including decorations, in physical pixels.
+    ///
+    /// The window lives as long as `WinitWindow` does, so this is always available. Once the
+    ///  platform destroyed the window, this is the last known size of the client area instead.
+    pub fn outer_size_physical(&self) -> PhysicalSize<u32> {
+        self.live_window()
+            .map_or(self.window_cache.inner_size, |window| window.outer_size())
+    }
+
+    /// Returns the window attributes as currently applied.
+    ///
+    /// The window is queried where the platform allows it, the title is the one last set.
+    pub fn current_attributes(&self) -> WindowAttributesSummary {
+        let window = self.live_window();
+        WindowAttributesSummary {
+            title: self.title.clone(),
+            size: self.size(),
+            position: self.get_position(),
+            resizable: window.map_or(self.resizable, |window| window.is_resizable()),
+            decorations: window.map_or(self.decorations, |window| window.is_decorated()),
+            fullscreen: window.map_or(false, |window| window.fullscreen().is_some()),
+            visible: window.and_then(|window| window.is_visible()),
+        }
+    }
+
+    /// Returns the outer position of the window from the most recent move, in physical pixels.
+    ///
+    /// This is `None` until the window has been moved. A position asked for with `set_position`
+    ///  only shows up here once the platform reports the move. `get_position` also uses it once
+    ///  available, so it doesn't need to ask the platform.
+    pub fn last_moved(&self) -> Option<Position> {
+        self.last_moved
+    }
+
+    /// Returns the monitor containing the mouse cursor.
+    ///
+    /// winit can't query the cursor position by itself, so this only knows where the cursor is
+    ///  while it's over the window, and returns `None` otherwise.
+    pub fn monitor_at_cursor(&self) -> Option<MonitorInfo> {
+        if !self.cursor_inside {
+            return None;
+        }
+        let origin = self.live_window()?.inner_position().ok()?;
+        let x = origin.x + self.cursor_physical.x as i32;
+        let y = origin.y + self.cursor_physical.y as i32;
+        self.event_loop
+            .available_monitors()
+            .find(|monitor| {
+                let position = monitor.position();
+                let size = monitor.size();
+                x >= position.x
+                    && y >= position.y
+                    && x < position.x + size.width as i32
+                    && y < position.y + size.height as i32
+            })
+            .map(|monitor| map_monitor(&monitor))
+    }
+
+    /// Returns the refresh rate of the monitor the window is on, in hertz.
+    ///
+    /// Returns `None` when the platform doesn't report it, which is common on Wayland.
+    pub fn refresh_rate_hz(&self) -> Option<f64> {
+        self.live_window()?
+            .current_monitor()
+            .and_then(|monitor| monitor.refresh_rate_millihertz())
+            .map(|millihertz| millihertz as f64 / 1000.0)
+    }
+
+    /// Returns whether the platform supports `feature`, on a best-effort basis.
+    ///
+    /// This follows the platform support documented by winit, so applications can hide toggles
+    ///  for features that would do nothing.
+    pub fn supports(&self, feature: WindowFeature) -> bool {
+        let windows = cfg!(target_os = "windows");
+        let macos = cfg!(target_os = "macos");
+        let x11 = x11::is_x11(&self.window);
+        let wayland = cfg!(all(
+            unix,
+            not(any(
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "android"
+            ))
+        )) && !x11;
+
+        match feature {
+            WindowFeature::ContentProtection => windows || macos,
+            WindowFeature::CursorHittest => windows || macos || wayland,
+            WindowFeature::CursorLock => macos || wayland,
+            WindowFeature::CursorConfine => windows || x11 || wayland,
+        }
+    }
+
+    /// Sets whether the window accepts input method (IME) input, used for CJK text and the
+    ///  like.
+    ///
+    /// Committed text is emitted as `Input::Text`, the text being composed is available from
+    ///  `ime_preedit`. IME input is disabled by default.
+    pub fn set_ime_allowed(&mut self, allowed: bool) {
+        if let Some(window) = self.live_window() {
+            window.set_ime_allowed(allowed);
+        }
+        if !allowed {
+            self.ime_preedit.clear();
+        }
+    }
+
+    /// Sets the area of the text caret, in physical pixels, so the input method can place its
+    ///  candidate box next to it.
+    ///
+    /// This only has an effect after `set_ime_allowed(true)`. winit only takes the position of
+    ///  the candidate box, so it's placed right below the area to keep the caret visible.
+    pub fn set_ime_cursor_area(&mut self, position: Position, size: Size) {
+        let position = PhysicalPosition::new(position.x as f64, position.y as f64 + size.height)
+            .to_logical::<f64>(self.scale_factor);
+        if let Some(window) = self.live_window() {
+            window.set_ime_position(position);
+        }
+    }
+
+    /// Tells the input method what kind of text is expected, so it can adapt.
+    ///
+    /// This is only supported on Wayland, where on-screen keyboards use it.
+    pub fn set_ime_purpose(&mut self, purpose: ImePurpose) {
+        self.ime_purpose = purpose;
+        if let Some(window) = self.live_window() {
+            window.set_ime_purpose(map_ime_purpose(purpose));
+        }
+    }
+
+    /// Returns the purpose last set with `set_ime_purpose`.
+    pub fn ime_purpose(&self) -> ImePurpose {
+        self.ime_purpose
+    }
+
+    /// Returns the text currently being composed with the input method, if any.
+    pub fn ime_preedit(&self) -> &str {
+        &self.ime_preedit
+    }
+
+    /// Sets the opacity of the whole window, clamped to `0.0..=1.0`.
+    ///
+    /// This is only applied on X11, through `_NET_WM_WINDOW_OPACITY`, and needs a compositing
+    ///  window manager to have a visible effect.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        if let Some(x11) = self.live_x11() {
+            x11.set_opacity(self.opacity);
+        }
+    }
+
+    /// Returns the opacity last set with `set_opacity`.
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// Requests the window to be shown on every virtual desktop.
+    ///
+    /// This is only applied on X11, by asking the window manager to move the window to
+    ///  desktop `0xffffffff` through `_NET_WM_DESKTOP`. Turning it off moves the window back
+    ///  to the current desktop.
+    pub fn set_visible_on_all_workspaces(&mut self, value: bool) {
+        self.visible_on_all_workspaces = value;
+        if let Some(x11) = self.live_x11() {
+            x11.set_visible_on_all_workspaces(value);
+        }
+    }
+
+    /// Returns the value last set with `set_visible_on_all_workspaces`.
+    pub fn visible_on_all_workspaces(&self) -> bool {
+        self.visible_on_all_workspaces
+    }
+
+    /// Makes `parent` the owner of the window, so it stays above it and minimizes along with it.
+    ///
+    /// This is only applied on X11, by marking the window as transient for `parent`. On Windows
+    ///  the owner can only be set when creating the window, through
+    ///  `WindowBuilderExtWindows::with_owner_window` in [`WinitWindow::try_new_with_builder`].
+    ///  Elsewhere this does nothing.
+    pub fn set_owner(&mut self, parent: &WinitWindow) {
+        if let (Some(x11), Some(parent)) = (self.live_x11(), parent.live_x11()) {
+            x11.set_transient_for(parent);
+        }
+    }
+
+    /// Keeps the screen saver from starting while `awake` is `true`.
+    ///
+    /// This is only applied on X11, by resetting the screen saver timer every 30 seconds while
+    ///  events are pumped. Nothing is held onto, so stopping to pump events or dropping the window
+    ///  lets the screen saver start again.
+    pub fn set_keep_awake(&mut self, awake: bool) {
+        self.keep_awake = awake;
+        self.last_awake_reset = None;
+    }
+
+    /// Returns the value last set with `set_keep_awake`.
+    pub fn keep_awake(&self) -> bool {
+        self.keep_awake
+    }
+
+    /// Sets the progress shown on the taskbar or dock icon, clamped to `0.0..=1.0`.
+    ///
+    /// `None` clears the indicator. winit 0.28 has no taskbar or dock API, so for now this has no
+    ///  visible effect.
+    pub fn set_progress(&mut self, progress: Option<f64>) {
+        self.progress = clamp_progress(progress);
+    }
+
+    /// Returns the progress last set with `set_progress`.
+    pub fn progress(&self) -> Option<f64> {
+        self.progress
+    }
+
+    /// Returns the Xlib connection, unless the window isn't on X11 or was destroyed.
+    fn live_x11(&self) -> Option<&x11::X11> {
+        self.live_window().and(self.x11.as_ref())
+    }
+
+    /// Sets where the cursor is held while it's captured, as a fraction of the draw area.
+    ///
+    /// Defaults to `[0.5, 0.5]`, the center of the window. Useful when the rendered viewport is
+    ///  letterboxed and doesn't share its center with the window.
+    pub fn set_capture_center(&mut self, fraction: [f64; 2]) {
+        self.capture_center = fraction;
+    }
+
+    /// The physical position the cursor is warped back to while captured.
+    fn capture_center(&self) -> PhysicalPosition<f64> {
+        let size = self.inner_size().cast::<f64>();
+        PhysicalPosition::new(
+            size.width * self.capture_center[0],
+            size.height * self.capture_center[1],
+        )
+    }
+
+    /// Render arguments for the current size of the window.
+    ///
+    /// The window doesn't know when the application last updated, so `ext_dt` is the time since
+    ///  the previous render event instead, or `0.0` for the first one.
+    fn render_args(&mut self) -> RenderArgs {
+        let now = Instant::now();
+        let ext_dt = self
+            .last_render
+            .map_or(0.0, |last| now.duration_since(last).as_secs_f64());
+        self.last_render = Some(now);
+
+        let size = self.size();
+        let draw_size = self.inner_size();
+        RenderArgs {
+            ext_dt,
+            window_size: [size.width, size.height],
+            draw_size: [draw_size.width, draw_size.height],
+        }
+    }
+
+    /// Emits a [`MONITOR_CHANGED`] event if the window is now on a different monitor.
+    fn update_monitor(&mut self) {
+        let monitor = match self.live_window() {
+            Some(window) => window.current_monitor(),
+            None => return,
+        };
+        if monitor == self.monitor {
+            return;
+        }
+
+        if let Some(ref monitor) = monitor {
+            self.queue.events.push_back(Event::Custom(
+                MONITOR_CHANGED,
+                Arc::new(map_monitor(monitor)),
+                None,
+            ));
+        }
+        self.monitor = monitor;
+    }
+
+    fn handle_event<T>(&mut self, event: winit::event::Event<T>, center: PhysicalPosition<f64>) {
+        let event = match event {
+            winit::event::Event::WindowEvent { event, .. } => event,
+            winit::event::Event::RedrawRequested(_) => {
+                // There's no point rendering what can't be seen
+                if !self.render_events || self.occluded {
+                    return;
+                }
+                let render = Event::Loop(Loop::Render(self.render_args()));
+                self.queue.events.push_back(render);
+                return;
+            }
+            _ => return,
+        };
+
+        self.window_cache.update(&event);
+
+        // Keep the untransformed cursor position around for monitor lookups
+        if let WindowEvent::CursorMoved { position, .. } = event {
+            self.cursor_physical = position;
+        }
+
+        // Special event handling.
+        // Some events are not exposed to user and handled internally.
+        match event {
+            WindowEvent::KeyboardInput { input, .. } if self.input_options.input_enabled => {
+                if self.exit_on_esc {
+                    if let Some(VirtualKeyCode::Escape) = input.virtual_keycode {
+                        self.set_should_close(true);
+                        return;
+                    }
+                }
+
+                self.modifier_keys_seen |= match input.virtual_keycode {
+                    Some(VirtualKeyCode::LShift) | Some(VirtualKeyCode::RShift) => {
+                        ModifiersState::SHIFT
+                    }
+                    Some(VirtualKeyCode::LControl) | Some(VirtualKeyCode::RControl) => {
+                        ModifiersState::CTRL
+                    }
+                    Some(VirtualKeyCode::LAlt) | Some(VirtualKeyCode::RAlt) => ModifiersState::ALT,
+                    _ => ModifiersState::empty(),
+                };
+
+                self.key_repeat.update(input.state, input.scancode);
+            }
+            WindowEvent::Occluded(occluded) => {
+                self.occluded = occluded;
+                // Render events stop while occluded, make sure they start again
+                if let (false, Some(window)) = (occluded, self.live_window()) {
+                    window.request_redraw();
+                }
+            }
+            WindowEvent::Moved(position) => {
+                self.last_moved = Some(Position {
+                    x: position.x,
+                    y: position.y,
+                });
+                self.requested_position = None;
+                self.monitor_dirty = true;
+            }
+            WindowEvent::DroppedFile(ref path) if self.file_drop_handler.is_some() => {
+                self.dropped_files.push(path.clone());
+            }
+            WindowEvent::HoveredFileCancelled => self.dropped_files.clear(),
+            // winit mustn't be called on the window anymore, not even to map the event
+            WindowEvent::Destroyed => {
+                self.last_error = Some("The window was destroyed by the platform".to_string());
+                self.queue_event(Event::Input(Input::Close(CloseArgs), None));
+                return;
+            }
+            WindowEvent::Ime(Ime::Preedit(ref text, _)) => self.ime_preedit = text.clone(),
+            WindowEvent::Ime(Ime::Commit(_)) | WindowEvent::Ime(Ime::Disabled) => {
+                self.ime_preedit.clear();
+            }
+            WindowEvent::Resized(_) => self.last_resize = Some(Instant::now()),
+            WindowEvent::ThemeChanged(theme) => {
+                self.theme = map_theme(theme);
+                self.queue.events.push_back(Event::Custom(
+                    THEME_CHANGED,
+                    Arc::new(self.theme),
+                    None,
+                ));
+            }
+            // The key events for the modifiers often arrive right after this, so buttons are only
+            //  made up for them once the pump is done
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers_changed |= self.input_options.input_enabled;
+                self.modifiers = modifiers;
+            }
+            WindowEvent::CursorEntered { .. } => self.cursor_inside = true,
+            WindowEvent::CursorLeft { .. } => self.cursor_inside = false,
+            WindowEvent::CursorMoved { position, .. }
+                if self.capture_cursor && self.input_options.input_enabled =>
+            {
+                self.queue.last_input = Instant::now();
+                let prev_last_cursor = self.last_cursor;
+                self.last_cursor = position.to_logical(self.scale_factor);
+
+                // Don't track distance if the position is at the center, this probably is
+                //  from cursor center lock, or irrelevant.
+                if position == center {
+                    return;
+                }
+
+                // Add the distance to the tracked cursor movement
+                self.cursor_accumulator.x += position.x - prev_last_cursor.x;
+                self.cursor_accumulator.y += position.y - prev_last_cursor.y;
+
+                return;
+            }
+            _ => {}
+        }
+
+        // Usual events are handled here and passed to user.
+        let draw_size = self.inner_size();
+        let event = filter_window_event(
+            event,
+            &self.input_options,
+            self.modifiers,
+            self.scale_factor,
+            draw_size,
+        );
+        if let Some(ev) = event {
+            // Hold on to resizes until the size settles, if debouncing
+            if let Event::Input(Input::Resize(_), _) = ev {
+                if self.resize_debounce > Duration::ZERO {
+                    self.pending_resize.hold(ev, Instant::now());
+                    return;
+                }
+            }
+
+            if self.suppress_button_for_text {
+                for ev in self.text_keys.filter(ev) {
+                    self.queue_event(ev);
+                }
+            } else {
+                self.queue_event(ev);
+            }
+        }
+    }
+
+    /// Pumps the winit event loop, queueing up all pending events.
+    ///
+    /// Does nothing after `shutdown` or while pumping is disabled.
+    fn pump_events(&mut self) {
+        if self.shut_down || !self.pumping_enabled {
+            return;
+        }
+
+        let center = self.capture_center();
+
+        // Add all events we got to the event queue, since winit only allows us to get all pending
+        //  events at once.
+        {
+            let mut events: Vec<winit::event::Event<UserEvent>> = Vec::new();
+            let event_loop_proxy = self.event_loop.create_proxy();
+            event_loop_proxy
+                .send_event(UserEvent::WakeUp)
+                .expect("Event loop is closed before property handling all events.");
+
+            let mut new_scale_factor = None;
+            let control_flow_setting = if self.continuous_rendering {
+                ControlFlow::Poll
+            } else {
+                ControlFlow::Wait
+            };
+            self.event_loop.run_return(|event, _, control_flow| {
+                // `to_static` drops scale factor changes, so take note of them before converting.
+                //  The window's size changes along with the scale factor, which is a resize as far
+                //  as the user is concerned.
+                if let winit::event::Event::WindowEvent {
+                    window_id,
+                    event:
+                        WindowEvent::ScaleFactorChanged {
+                            scale_factor,
+                            ref new_inner_size,
+                        },
+                } = event
+                {
+                    new_scale_factor = Some(scale_factor);
+                    events.push(winit::event::Event::WindowEvent {
+                        window_id,
+                        event: WindowEvent::Resized(**new_inner_size),
+                    });
+                }
+                if let Some(e) = event.to_static() {
+                    if e == winit::event::Event::UserEvent(UserEvent::WakeUp) {
+                        *control_flow = ControlFlow::Exit;
+                        return;
+                    }
+                    events.push(e);
+                }
+                *control_flow = control_flow_setting;
+            });
+            self.last_pump_stats = pump_stats(&events, control_flow_setting == ControlFlow::Poll);
+            if let Some(scale_factor) = new_scale_factor {
+                self.scale_factor = scale_factor;
+            }
+            for event in events.into_iter() {
+                self.handle_event(event, center)
+            }
+            if self.modifiers_changed {
+                self.modifiers_changed = false;
+                self.queue_modifier_buttons();
+            }
+            self.modifier_keys_seen = ModifiersState::empty();
+            self.flush_held_key_press();
+            self.flush_dropped_files();
+
+            // Keep render events coming even when there's no input to react to. This is the point
+            //  where winit would be about to wait for more events, if we let it.
+            self.request_continuous_redraw();
+
+            // Moving between monitors shows up as a move or a scale factor change
+            if new_scale_factor.is_some() || self.monitor_dirty {
+                self.monitor_dirty = false;
+                self.update_monitor();
+            }
+        }
+
+        if self.keep_awake
+            && self
+                .last_awake_reset
+                .map_or(true, |reset| reset.elapsed() >= Duration::from_secs(30))
+        {
+            if let Some(x11) = self.live_x11() {
+                x11.reset_screen_saver();
+            }
+            self.last_awake_reset = Some(Instant::now());
+        }
+
+        // Release a held resize once the window has been stable for long enough
+        if let Some(resize) = self
+            .pending_resize
+            .take_settled(self.resize_debounce, Instant::now())
+        {
+            self.queue.events.push_back(resize);
+        }
+    }
+
+    fn flush_dropped_files(&mut self) {
+        if self.dropped_files.is_empty() {
+            return;
+        }
+        let paths = std::mem::take(&mut self.dropped_files);
+        let position = Position {
+            x: self.queue.cursor_position[0] as i32,
+            y: self.queue.cursor_position[1] as i32,
+        };
+        if let Some(handler) = self.file_drop_handler.as_mut() {
+            handler(paths, position);
+        }
+    }
+
+    /// Throttles frames to the refresh rate when vsync was requested but isn't happening.
+    fn limit_frame_rate(&mut self) {
+        if self.vsync && self.vsync_fallback_limiter {
+            let refresh_rate = self.refresh_rate_hz().unwrap_or(60.0);
+            let interval = Duration::from_secs_f64(1.0 / refresh_rate);
+            if let Some(last_swap) = self.last_swap {
+                // Frames a little faster than the refresh rate are just timing noise
+                let elapsed = last_swap.elapsed();
+                if elapsed < interval / 2 {
+                    std::thread::sleep(interval - elapsed);
+                }
+            }
+        }
+        self.last_swap = Some(Instant::now());
+    }
+
+    /// Requests a redraw when rendering continuously and the window can be seen.
+    fn request_continuous_redraw(&self) {
+        let window = match self.live_window() {
+            Some(window) => window,
+            None => return,
+        };
+        if !self.continuous_rendering || self.occluded || window.is_minimized() == Some(true) {
+            return;
+        }
+        window.request_redraw();
+    }
+
+    /// Remembers the error of a failed operation for `take_last_error`.
+    fn record_error<E: fmt::Display>(&mut self, result: Result<(), E>) {
+        if let Err(err) = result {
+            self.last_error = Some(err.to_string());
+        }
+    }
+
+    /// Queues an event for the user.
+    fn queue_event(&mut self, event: Event) {
+        self.queue.push(event);
+    }
+
+    /// Queues button events for the modifier keys that don't match the current modifiers, see
+    ///  `modifier_key_events`.
+    fn queue_modifier_buttons(&mut self) {
+        let events = modifier_key_events(
+            &self.queue.pressed_buttons,
+            self.modifiers,
+            self.modifier_keys_seen,
+        );
+        if !events.is_empty() {
+            self.flush_held_key_press();
+        }
+        for event in events {
+            self.queue_event(event);
+        }
+    }
+
+    /// Queues a key press held back by the `suppress_button_for_text` option.
+    fn flush_held_key_press(&mut self) {
+        if let Some(press) = self.text_keys.flush() {
+            self.queue_event(press);
+        }
+    }
+}
+
+impl Window for WinitWindow {
+    fn set_should_close(&mut self, value: bool) {
+        self.should_close = value;
+    }
+
+    fn should_close(&self) -> bool {
+        self.should_close
+    }
+
+    fn size(&self) -> Size {
+        let (w, h): (u32, u32) = self.inner_size().into();
+        let hidpi = self.scale_factor;
+        ((w as f64 / hidpi) as u32, (h as f64 / hidpi) as u32).into()
+    }
+
+    fn swap_buffers(&mut self) {
+        // This window backend was made for use with a vulkan renderer that handles swapping by
+        //  itself, if you need it here open up an issue. What we can use this for however is
+        //  detecting the end of a frame, which we can use to gather up cursor_accumulator data.
+
+        self.limit_frame_rate();
+
+        // The input snapshot's per-frame data starts over for the next frame
+        self.queue.frame_scroll = [0.0, 0.0];
+        self.queue.frame_text.clear();
+
+        // Nothing is presented while the window can't be seen, so don't fight over the cursor
+        let hidden = self
+            .live_window()
+            .map_or(true, |window| window.is_minimized() == Some(true));
+        if hidden || self.occluded {
+            self.cursor_accumulator = LogicalPosition::new(0.0, 0.0);
+            return;
+        }
+
+        if self.capture_cursor {
+            let center = self.capture_center();
+
+            // Center-lock the cursor if we're using capture_cursor
+            if self.skip_capture_warp {
+                self.skip_capture_warp = false;
+            } else {
+                let result = self.get_window().set_cursor_position(center);
+                self.record_error(result);
+            }
+
+            // Create a relative input based on the distance from the center
+            self.queue.events.push_back(Event::Input(
+                Input::Move(Motion::MouseRelative([
+                    self.cursor_accumulator.x,
+                    if self.input_options.flip_y {
+                        -self.cursor_accumulator.y
+                    } else {
+                        self.cursor_accumulator.y
+                    },
+                ])),
+                None,
+            ));
+
+            self.cursor_accumulator = LogicalPosition::new(0.0, 0.0);
+        }
+    }
+
+    fn wait_event(&mut self) -> Event {
+        // TODO: Implement this
+        unimplemented!()
+    }
+
+    fn wait_event_timeout(&mut self, _timeout: Duration) -> Option<Event> {
+        // TODO: Implement this
+        unimplemented!()
+    }
+
+    fn poll_event(&mut self) -> Option<Event> {
+        self.pump_events();
+
+        // Get the first event in the queue
+        let event = self.queue.events.pop_front();
+
+        // Check if we got a close event, if we did we need to mark ourselves as should-close
+        if let &Some(Event::Input(Input::Close(_), ..)) = &event {
+            self.set_should_close(true);
+        }
+
+        event
+    }
+
+    fn draw_size(&self) -> Size {
+        let size: (f64, f64) = self.inner_size().into();
+        size.into()
+    }
+}
+
+impl AdvancedWindow for WinitWindow {
+    fn get_title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn set_title(&mut self, value: String) {
+        if let Some(window) = self.live_window() {
+            window.set_title(&value);
+        }
+        self.title = value;
+    }
+
+    fn get_exit_on_esc(&self) -> bool {
+        self.exit_on_esc
+    }
+
+    fn set_exit_on_esc(&mut self, value: bool) {
+        self.exit_on_esc = value
+    }
+
+    fn set_capture_cursor(&mut self, value: bool) {
+        let result = self.try_set_capture_cursor(value);
+        let grab_failed = value && result.is_err();
+        self.record_error(result);
+        if !grab_failed || self.window_cache.destroyed {
+            return;
+        }
+
+        // Not every platform can lock the cursor, confining it still keeps it from wandering
+        //  off. Failing that, the warp to the center in `swap_buffers` is all we've got.
+        let mode = match self.capture_grab {
+            CursorGrab::Locked
+                if self
+                    .get_window()
+                    .set_cursor_grab(CursorGrabMode::Confined)
+                    .is_ok() =>
+            {
+                CursorGrab::Confined
+            }
+            _ => CursorGrab::None,
+        };
+        self.start_capture(mode);
+    }
+
+    fn get_automatic_close(&self) -> bool {
+        false
+    }
+
+    fn set_automatic_close(&mut self, _value: bool) {
+        // TODO: Implement this
+    }
+
+    fn show(&mut self) {
+        if let Some(window) = self.live_window() {
+            window.set_visible(true);
+        }
+    }
+
+    fn hide(&mut self) {
+        if let Some(window) = self.live_window() {
+            window.set_visible(false);
+        }
+    }
+
+    fn get_position(&self) -> Option<Position> {
+        if let Some(position) = self.requested_position.or(self.last_moved) {
+            return Some(position);
+        }
+        self.live_window()?
+            .outer_position()
+            .map(|p| Position { x: p.x, y: p.y })
+            .ok()
+    }
+
+    fn set_position<P: Into<Position>>(&mut self, val: P) {
+        // Positions are in physical pixels, the same space `get_position` and `Moved` report in
+        let val = val.into();
+        if let Some(window) = self.live_window() {
+            window.set_outer_position(PhysicalPosition::new(val.x, val.y));
+        }
+        // Don't let `get_position` report the old position until the `Moved` event comes
+        self.requested_position = Some(val);
+    }
+
+    fn set_size<S: Into<Size>>(&mut self, size: S) {
+        let size: Size = size.into();
+        let hidpi = self.scale_factor;
+        if let Some(window) = self.live_window() {
+            window.set_inner_size(LogicalSize::new(size.width * hidpi, size.height * hidpi));
+        }
+    }
+}
+
+// Lets graphics libraries like wgpu create surfaces for the window
+unsafe impl HasRawWindowHandle for WinitWindow {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        self.window.raw_window_handle()
+    }
+}
+
+unsafe impl HasRawDisplayHandle for WinitWindow {
+    fn raw_display_handle(&self) -> RawDisplayHandle {
+        self.window.raw_display_handle()
+    }
+}
+
+#[cfg(not(feature = "use-vulkano"))]
+impl BuildFromWindowSettings for WinitWindow {
+    fn build_from_window_settings(settings: &WindowSettings) -> Result<Self, Box<dyn Error>> {
+        Self::try_new(settings)
+    }
+}
+
+/// Whether `event` is keyboard, mouse, touch or text input from the user, rather than a window
+///  state change like a close, resize, focus or cursor enter.
+fn is_user_input(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Input(Input::Button(_), _)
+            | Event::Input(Input::Move(_), _)
+            | Event::Input(Input::Text(_), _)
+    )
+}
+
+fn map_key(input: &KeyboardInput) -> Key {
+    use winit::event::VirtualKeyCode::*;
+    // TODO: Complete the lookup match
+    if let Some(vk) = input.virtual_keycode {
+        match vk {
+            Key1 => Key::D1,
+            Key2 => Key::D2,
+            Key3 => Key::D3,
+            Key4 => Key::D4,
+            Key5 => Key::D5,
+            Key6 => Key::D6,
+            Key7 => Key::D7,
+            Key8 => Key::D8,
+            Key9 => Key::D9,
+            Key0 => Key::D0,
+            A => Key::A,
+            B => Key::B,
+            C => Key::C,
+            D => Key::D,
+            E => Key::E,
+            F => Key::F,
+            G => Key::G,
+            H => Key::H,
+            I => Key::I,
+            J => Key::J,
+            K => Key::K,
+            L => Key::L,
             M => Key::M,
             N => Key::N,
             O => Key::O,
@@ -373,72 +2138,932 @@ fn map_key(input: &KeyboardInput) -> Key {
             F14 => Key::F14,
             F15 => Key::F15,
 
-            Delete => Key::Delete,
+            Numpad0 => Key::NumPad0,
+            Numpad1 => Key::NumPad1,
+            Numpad2 => Key::NumPad2,
+            Numpad3 => Key::NumPad3,
+            Numpad4 => Key::NumPad4,
+            Numpad5 => Key::NumPad5,
+            Numpad6 => Key::NumPad6,
+            Numpad7 => Key::NumPad7,
+            Numpad8 => Key::NumPad8,
+            Numpad9 => Key::NumPad9,
+            NumpadAdd => Key::NumPadPlus,
+            NumpadSubtract => Key::NumPadMinus,
+            NumpadMultiply => Key::NumPadMultiply,
+            NumpadDivide => Key::NumPadDivide,
+            NumpadDecimal => Key::NumPadPeriod,
+            NumpadComma => Key::NumPadComma,
+            NumpadEquals => Key::NumPadEquals,
+            NumpadEnter => Key::NumPadEnter,
+
+            Capital => Key::CapsLock,
+            Numlock => Key::NumLockClear,
+            Scroll => Key::ScrollLock,
+            Snapshot => Key::PrintScreen,
+
+            Insert => Key::Insert,
+            Delete => Key::Delete,
+            Home => Key::Home,
+            End => Key::End,
+            PageUp => Key::PageUp,
+            PageDown => Key::PageDown,
+
+            Left => Key::Left,
+            Up => Key::Up,
+            Right => Key::Right,
+            Down => Key::Down,
+
+            Back => Key::Backspace,
+            Return => Key::Return,
+            Space => Key::Space,
+
+            LAlt => Key::LAlt,
+            LControl => Key::LCtrl,
+            LWin => Key::Menu,
+            LShift => Key::LShift,
+
+            RAlt => Key::LAlt,
+            RControl => Key::RCtrl,
+            RWin => Key::Menu,
+            RShift => Key::RShift,
+
+            Tab => Key::Tab,
+            _ => Key::Unknown,
+        }
+    } else {
+        Key::Unknown
+    }
+}
+
+/// Maps a scancode to the key at that position on a US PC keyboard layout.
+///
+/// Linux and Windows both report these set 1 scancodes for the main block of the keyboard and the
+///  number pad. The other keys differ between them, see `map_extended_scancode`. macOS uses
+///  unrelated codes, so nothing is mapped there.
+fn map_scancode(scancode: i32) -> Key {
+    if cfg!(any(target_os = "macos", target_os = "ios")) {
+        return Key::Unknown;
+    }
+
+    match scancode {
+        1 => Key::Escape,
+        2 => Key::D1,
+        3 => Key::D2,
+        4 => Key::D3,
+        5 => Key::D4,
+        6 => Key::D5,
+        7 => Key::D6,
+        8 => Key::D7,
+        9 => Key::D8,
+        10 => Key::D9,
+        11 => Key::D0,
+        12 => Key::Minus,
+        13 => Key::Equals,
+        14 => Key::Backspace,
+        15 => Key::Tab,
+        16 => Key::Q,
+        17 => Key::W,
+        18 => Key::E,
+        19 => Key::R,
+        20 => Key::T,
+        21 => Key::Y,
+        22 => Key::U,
+        23 => Key::I,
+        24 => Key::O,
+        25 => Key::P,
+        26 => Key::LeftBracket,
+        27 => Key::RightBracket,
+        28 => Key::Return,
+        29 => Key::LCtrl,
+        30 => Key::A,
+        31 => Key::S,
+        32 => Key::D,
+        33 => Key::F,
+        34 => Key::G,
+        35 => Key::H,
+        36 => Key::J,
+        37 => Key::K,
+        38 => Key::L,
+        39 => Key::Semicolon,
+        40 => Key::Quote,
+        41 => Key::Backquote,
+        42 => Key::LShift,
+        43 => Key::Backslash,
+        44 => Key::Z,
+        45 => Key::X,
+        46 => Key::C,
+        47 => Key::V,
+        48 => Key::B,
+        49 => Key::N,
+        50 => Key::M,
+        51 => Key::Comma,
+        52 => Key::Period,
+        53 => Key::Slash,
+        54 => Key::RShift,
+        55 => Key::NumPadMultiply,
+        56 => Key::LAlt,
+        57 => Key::Space,
+        58 => Key::CapsLock,
+        59 => Key::F1,
+        60 => Key::F2,
+        61 => Key::F3,
+        62 => Key::F4,
+        63 => Key::F5,
+        64 => Key::F6,
+        65 => Key::F7,
+        66 => Key::F8,
+        67 => Key::F9,
+        68 => Key::F10,
+        69 => Key::NumLockClear,
+        70 => Key::ScrollLock,
+        71 => Key::NumPad7,
+        72 => Key::NumPad8,
+        73 => Key::NumPad9,
+        74 => Key::NumPadMinus,
+        75 => Key::NumPad4,
+        76 => Key::NumPad5,
+        77 => Key::NumPad6,
+        78 => Key::NumPadPlus,
+        79 => Key::NumPad1,
+        80 => Key::NumPad2,
+        81 => Key::NumPad3,
+        82 => Key::NumPad0,
+        83 => Key::NumPadPeriod,
+        87 => Key::F11,
+        88 => Key::F12,
+        _ => map_extended_scancode(scancode),
+    }
+}
+
+/// Maps the scancodes of keys outside the main block and number pad.
+///
+/// Windows reports the set 1 scancodes of these keys with their `0xE0` prefix. Linux reports
+///  evdev codes, which have their own values here.
+fn map_extended_scancode(scancode: i32) -> Key {
+    if cfg!(windows) {
+        match scancode {
+            0x64 => Key::F13,
+            0x65 => Key::F14,
+            0x66 => Key::F15,
+            0xE01C => Key::NumPadEnter,
+            0xE01D => Key::RCtrl,
+            0xE035 => Key::NumPadDivide,
+            0xE038 => Key::RAlt,
+            0xE047 => Key::Home,
+            0xE048 => Key::Up,
+            0xE049 => Key::PageUp,
+            0xE04B => Key::Left,
+            0xE04D => Key::Right,
+            0xE04F => Key::End,
+            0xE050 => Key::Down,
+            0xE051 => Key::PageDown,
+            0xE052 => Key::Insert,
+            0xE053 => Key::Delete,
+            0xE05B => Key::LGui,
+            0xE05C => Key::RGui,
+            _ => Key::Unknown,
+        }
+    } else {
+        match scancode {
+            96 => Key::NumPadEnter,
+            97 => Key::RCtrl,
+            98 => Key::NumPadDivide,
+            100 => Key::RAlt,
+            102 => Key::Home,
+            103 => Key::Up,
+            104 => Key::PageUp,
+            105 => Key::Left,
+            106 => Key::Right,
+            107 => Key::End,
+            108 => Key::Down,
+            109 => Key::PageDown,
+            110 => Key::Insert,
+            111 => Key::Delete,
+            125 => Key::LGui,
+            126 => Key::RGui,
+            183 => Key::F13,
+            184 => Key::F14,
+            185 => Key::F15,
+            _ => Key::Unknown,
+        }
+    }
+}
+
+/// Maps a scancode to its SDL2 scancode, which are USB HID usage ids.
+///
+/// This goes through the physical layout table of `map_scancode`, so it covers the same keys.
+fn map_sdl_scancode(scancode: i32) -> Option<i32> {
+    let sdl_scancode = match map_scancode(scancode) {
+        Key::A => 4,
+        Key::B => 5,
+        Key::C => 6,
+        Key::D => 7,
+        Key::E => 8,
+        Key::F => 9,
+        Key::G => 10,
+        Key::H => 11,
+        Key::I => 12,
+        Key::J => 13,
+        Key::K => 14,
+        Key::L => 15,
+        Key::M => 16,
+        Key::N => 17,
+        Key::O => 18,
+        Key::P => 19,
+        Key::Q => 20,
+        Key::R => 21,
+        Key::S => 22,
+        Key::T => 23,
+        Key::U => 24,
+        Key::V => 25,
+        Key::W => 26,
+        Key::X => 27,
+        Key::Y => 28,
+        Key::Z => 29,
+        Key::D1 => 30,
+        Key::D2 => 31,
+        Key::D3 => 32,
+        Key::D4 => 33,
+        Key::D5 => 34,
+        Key::D6 => 35,
+        Key::D7 => 36,
+        Key::D8 => 37,
+        Key::D9 => 38,
+        Key::D0 => 39,
+        Key::Return => 40,
+        Key::Escape => 41,
+        Key::Backspace => 42,
+        Key::Tab => 43,
+        Key::Space => 44,
+        Key::Minus => 45,
+        Key::Equals => 46,
+        Key::LeftBracket => 47,
+        Key::RightBracket => 48,
+        Key::Backslash => 49,
+        Key::Semicolon => 51,
+        Key::Quote => 52,
+        Key::Backquote => 53,
+        Key::Comma => 54,
+        Key::Period => 55,
+        Key::Slash => 56,
+        Key::CapsLock => 57,
+        Key::F1 => 58,
+        Key::F2 => 59,
+        Key::F3 => 60,
+        Key::F4 => 61,
+        Key::F5 => 62,
+        Key::F6 => 63,
+        Key::F7 => 64,
+        Key::F8 => 65,
+        Key::F9 => 66,
+        Key::F10 => 67,
+        Key::F11 => 68,
+        Key::F12 => 69,
+        Key::ScrollLock => 71,
+        Key::Insert => 73,
+        Key::Home => 74,
+        Key::PageUp => 75,
+        Key::Delete => 76,
+        Key::End => 77,
+        Key::PageDown => 78,
+        Key::Right => 79,
+        Key::Left => 80,
+        Key::Down => 81,
+        Key::Up => 82,
+        Key::NumLockClear => 83,
+        Key::NumPadDivide => 84,
+        Key::NumPadMultiply => 85,
+        Key::NumPadMinus => 86,
+        Key::NumPadPlus => 87,
+        Key::NumPadEnter => 88,
+        Key::NumPad1 => 89,
+        Key::NumPad2 => 90,
+        Key::NumPad3 => 91,
+        Key::NumPad4 => 92,
+        Key::NumPad5 => 93,
+        Key::NumPad6 => 94,
+        Key::NumPad7 => 95,
+        Key::NumPad8 => 96,
+        Key::NumPad9 => 97,
+        Key::NumPad0 => 98,
+        Key::NumPadPeriod => 99,
+        Key::F13 => 104,
+        Key::F14 => 105,
+        Key::F15 => 106,
+        Key::LCtrl => 224,
+        Key::LShift => 225,
+        Key::LAlt => 226,
+        Key::LGui => 227,
+        Key::RCtrl => 228,
+        Key::RShift => 229,
+        Key::RAlt => 230,
+        Key::RGui => 231,
+        _ => return None,
+    };
+    Some(sdl_scancode)
+}
+
+fn map_keyboard_input(input: &KeyboardInput) -> Event {
+    let key = map_key(input);
+
+    let state = if input.state == ElementState::Pressed {
+        ButtonState::Press
+    } else {
+        ButtonState::Release
+    };
+
+    Event::Input(
+        Input::Button(ButtonArgs {
+            state,
+            button: Button::Keyboard(key),
+            scancode: Some(input.scancode as i32),
+        }),
+        None,
+    )
+}
+
+fn map_mouse_button(button: WinitMouseButton) -> MouseButton {
+    match button {
+        WinitMouseButton::Left => MouseButton::Left,
+        WinitMouseButton::Right => MouseButton::Right,
+        WinitMouseButton::Middle => MouseButton::Middle,
+        WinitMouseButton::Other(4) => MouseButton::X1,
+        WinitMouseButton::Other(5) => MouseButton::X2,
+        WinitMouseButton::Other(6) => MouseButton::Button6,
+        WinitMouseButton::Other(7) => MouseButton::Button7,
+        WinitMouseButton::Other(8) => MouseButton::Button8,
+        _ => MouseButton::Unknown,
+    }
+}
+
+fn map_monitor(monitor: &MonitorHandle) -> MonitorInfo {
+    let position = monitor.position();
+    let size = monitor.size();
+    MonitorInfo {
+        name: monitor.name(),
+        position: Position {
+            x: position.x,
+            y: position.y,
+        },
+        size: Size {
+            width: size.width as f64,
+            height: size.height as f64,
+        },
+        scale_factor: monitor.scale_factor(),
+        refresh_rate_millihertz: monitor.refresh_rate_millihertz(),
+    }
+}
+
+/// Clamps `progress` to `0.0..=1.0`, treating NaN as no progress.
+fn clamp_progress(progress: Option<f64>) -> Option<f64> {
+    progress
+        .filter(|progress| !progress.is_nan())
+        .map(|progress| progress.clamp(0.0, 1.0))
+}
+
+/// Checks that `rgba` holds exactly 4 bytes for each of the `width` times `height` pixels.
+fn check_icon_size(rgba: &[u8], width: u32, height: u32) -> Result<(), BadIcon> {
+    if rgba.len() % 4 != 0 {
+        return Err(BadIcon::ByteCountNotDivisibleBy4 {
+            byte_count: rgba.len(),
+        });
+    }
+    let width_x_height = width as u64 * height as u64;
+    let pixel_count = rgba.len() / 4;
+    if pixel_count as u64 != width_x_height {
+        return Err(BadIcon::DimensionsVsPixelCount {
+            width,
+            height,
+            width_x_height: width_x_height as usize,
+            pixel_count,
+        });
+    }
+    Ok(())
+}
+
+fn map_theme(theme: winit::window::Theme) -> Theme {
+    match theme {
+        winit::window::Theme::Light => Theme::Light,
+        winit::window::Theme::Dark => Theme::Dark,
+    }
+}
+
+fn map_cursor_grab(grab: CursorGrab) -> CursorGrabMode {
+    match grab {
+        CursorGrab::None => CursorGrabMode::None,
+        CursorGrab::Confined => CursorGrabMode::Confined,
+        CursorGrab::Locked => CursorGrabMode::Locked,
+    }
+}
+
+fn map_window_level(level: WindowLevel) -> winit::window::WindowLevel {
+    match level {
+        WindowLevel::Normal => winit::window::WindowLevel::Normal,
+        WindowLevel::AlwaysOnTop => winit::window::WindowLevel::AlwaysOnTop,
+        WindowLevel::AlwaysOnBottom => winit::window::WindowLevel::AlwaysOnBottom,
+    }
+}
+
+fn map_ime_purpose(purpose: ImePurpose) -> winit::window::ImePurpose {
+    match purpose {
+        ImePurpose::Normal => winit::window::ImePurpose::Normal,
+        ImePurpose::Password => winit::window::ImePurpose::Password,
+        ImePurpose::Terminal => winit::window::ImePurpose::Terminal,
+    }
+}
+
+fn map_cursor(cursor: CursorIcon) -> WinitCursorIcon {
+    match cursor {
+        CursorIcon::Arrow => WinitCursorIcon::Default,
+        CursorIcon::Text => WinitCursorIcon::Text,
+        CursorIcon::VerticalText => WinitCursorIcon::VerticalText,
+        CursorIcon::Crosshair => WinitCursorIcon::Crosshair,
+        CursorIcon::Hand => WinitCursorIcon::Hand,
+        CursorIcon::Help => WinitCursorIcon::Help,
+        CursorIcon::Wait => WinitCursorIcon::Wait,
+        CursorIcon::Progress => WinitCursorIcon::Progress,
+        CursorIcon::NotAllowed => WinitCursorIcon::NotAllowed,
+        CursorIcon::ContextMenu => WinitCursorIcon::ContextMenu,
+        CursorIcon::Cell => WinitCursorIcon::Cell,
+        CursorIcon::Alias => WinitCursorIcon::Alias,
+        CursorIcon::Copy => WinitCursorIcon::Copy,
+        CursorIcon::NoDrop => WinitCursorIcon::NoDrop,
+        CursorIcon::Move => WinitCursorIcon::Move,
+        CursorIcon::Grab => WinitCursorIcon::Grab,
+        CursorIcon::Grabbing => WinitCursorIcon::Grabbing,
+        CursorIcon::AllScroll => WinitCursorIcon::AllScroll,
+        CursorIcon::ZoomIn => WinitCursorIcon::ZoomIn,
+        CursorIcon::ZoomOut => WinitCursorIcon::ZoomOut,
+        CursorIcon::EResize => WinitCursorIcon::EResize,
+        CursorIcon::NResize => WinitCursorIcon::NResize,
+        CursorIcon::NeResize => WinitCursorIcon::NeResize,
+        CursorIcon::NwResize => WinitCursorIcon::NwResize,
+        CursorIcon::SResize => WinitCursorIcon::SResize,
+        CursorIcon::SeResize => WinitCursorIcon::SeResize,
+        CursorIcon::SwResize => WinitCursorIcon::SwResize,
+        CursorIcon::WResize => WinitCursorIcon::WResize,
+        CursorIcon::EwResize => WinitCursorIcon::EwResize,
+        CursorIcon::NsResize => WinitCursorIcon::NsResize,
+        CursorIcon::NeswResize => WinitCursorIcon::NeswResize,
+        CursorIcon::NwseResize => WinitCursorIcon::NwseResize,
+        CursorIcon::ColResize => WinitCursorIcon::ColResize,
+        CursorIcon::RowResize => WinitCursorIcon::RowResize,
+    }
+}
+
+fn map_touch(touch: WinitTouch, draw_size: PhysicalSize<u32>) -> Event {
+    // Piston expects touch positions normalized to the draw area, a minimized window has none
+    let normalize = |position: f64, extent: u32| {
+        if extent == 0 {
+            0.0
+        } else {
+            position / extent as f64
+        }
+    };
+    let position = [
+        normalize(touch.location.x, draw_size.width),
+        normalize(touch.location.y, draw_size.height),
+    ];
+
+    // Touch device ids are opaque in winit, hash them into something piston can hold
+    let mut hasher = DefaultHasher::new();
+    touch.device_id.hash(&mut hasher);
+    let device = hasher.finish() as i64;
+
+    // Most platforms don't report pressure, count those touches as full pressure
+    let pressure = touch.force.map_or(1.0, |force| force.normalized());
+
+    let phase = match touch.phase {
+        TouchPhase::Started => Touch::Start,
+        TouchPhase::Moved => Touch::Move,
+        TouchPhase::Ended => Touch::End,
+        TouchPhase::Cancelled => Touch::Cancel,
+    };
+
+    Event::Input(
+        Input::Move(Motion::Touch(TouchArgs::new(
+            device,
+            touch.id as i64,
+            position,
+            pressure,
+            phase,
+        ))),
+        None,
+    )
+}
+
+/// Settings for how window events are turned into input events, see `filter_window_event`.
+struct InputOptions {
+    input_enabled: bool,
+    extra_key_map: HashMap<i32, Key>,
+    sdl_scancodes: bool,
+    resize_in_physical: bool,
+    viewport: Option<(Position, Size)>,
+    flip_y: bool,
+    shift_scrolls_horizontal: bool,
+    scroll_dead_zone: f64,
+    ignore_unknown_mouse_buttons: bool,
+    control_chars_as_text: bool,
+}
+
+impl Default for InputOptions {
+    fn default() -> Self {
+        InputOptions {
+            input_enabled: true,
+            extra_key_map: HashMap::new(),
+            sdl_scancodes: false,
+            resize_in_physical: false,
+            viewport: None,
+            flip_y: false,
+            shift_scrolls_horizontal: false,
+            scroll_dead_zone: 0.0,
+            ignore_unknown_mouse_buttons: false,
+            control_chars_as_text: false,
+        }
+    }
+}
+
+/// Maps a window event to a Piston event and applies the user's input options to it.
+///
+/// Returns `None` when the event has no Piston equivalent or the options drop it.
+fn filter_window_event(
+    event: WindowEvent,
+    options: &InputOptions,
+    modifiers: ModifiersState,
+    scale_factor: f64,
+    draw_size: PhysicalSize<u32>,
+) -> Option<Event> {
+    let control_text = match event {
+        _ if !options.control_chars_as_text => None,
+        // Platforms disagree on what Enter types, settle on a newline
+        WindowEvent::ReceivedCharacter('\r') | WindowEvent::ReceivedCharacter('\n') => Some("\n"),
+        WindowEvent::ReceivedCharacter('\t') => Some("\t"),
+        WindowEvent::ReceivedCharacter('\u{8}') => Some("\u{8}"),
+        _ => None,
+    };
+    let ev = match control_text {
+        Some(text) => Some(Event::Input(Input::Text(text.to_string()), None)),
+        None => map_window_event(event, draw_size),
+    };
+    let mut ev = ev?;
+
+    // Give the user's mapping a chance at keys we couldn't map ourselves
+    if let Event::Input(Input::Button(ref mut args), _) = ev {
+        if let (Button::Keyboard(Key::Unknown), Some(scancode)) = (args.button, args.scancode) {
+            if let Some(&key) = options.extra_key_map.get(&scancode) {
+                args.button = Button::Keyboard(key);
+            }
+        }
+    }
+
+    if options.sdl_scancodes {
+        if let Event::Input(Input::Button(ref mut args), _) = ev {
+            args.scancode = args.scancode.and_then(map_sdl_scancode);
+        }
+    }
+
+    // `map_window_event` reports both sizes in physical pixels, the window size is made
+    //  logical unless asked not to
+    if let Event::Input(Input::Resize(ref mut args), _) = ev {
+        if !options.resize_in_physical {
+            args.window_size[0] /= scale_factor;
+            args.window_size[1] /= scale_factor;
+        }
+    }
+
+    if let (Event::Input(Input::Move(Motion::MouseCursor(ref mut position)), _), Some(viewport)) =
+        (&mut ev, options.viewport)
+    {
+        let (offset, size) = viewport;
+        position[0] = (position[0] - offset.x as f64).clamp(0.0, size.width);
+        position[1] = (position[1] - offset.y as f64).clamp(0.0, size.height);
+    }
+
+    if options.flip_y {
+        match ev {
+            Event::Input(Input::Move(Motion::MouseCursor(ref mut position)), _) => {
+                let height = match options.viewport {
+                    Some((_, size)) => size.height,
+                    None => draw_size.height as f64,
+                };
+                position[1] = height - position[1];
+            }
+            Event::Input(Input::Move(Motion::Touch(ref mut args)), _) => {
+                args.position_3d[1] = 1.0 - args.position_3d[1];
+            }
+            _ => {}
+        }
+    }
+
+    if let Event::Input(Input::Move(Motion::MouseScroll(ref mut delta)), _) = ev {
+        if options.shift_scrolls_horizontal && modifiers.shift() {
+            delta.swap(0, 1);
+        }
+    }
+
+    // Drop tiny scroll deltas, trackpads produce these as jitter when at rest
+    if let Event::Input(Input::Move(Motion::MouseScroll([x, y])), _) = ev {
+        if x.hypot(y) < options.scroll_dead_zone {
+            return None;
+        }
+    }
+
+    if options.ignore_unknown_mouse_buttons {
+        if let Event::Input(Input::Button(ButtonArgs { button, .. }), _) = ev {
+            if button == Button::Mouse(MouseButton::Unknown) {
+                return None;
+            }
+        }
+    }
+
+    if !options.input_enabled && is_user_input(&ev) {
+        return None;
+    }
+
+    Some(ev)
+}
+
+/// What's known about the window from its events, for once winit can't be asked anymore.
+struct WindowCache {
+    inner_size: PhysicalSize<u32>,
+    /// Whether the platform destroyed the window, after which calling winit on it may panic
+    destroyed: bool,
+}
+
+impl WindowCache {
+    fn new(inner_size: PhysicalSize<u32>) -> Self {
+        WindowCache {
+            inner_size,
+            destroyed: false,
+        }
+    }
+
+    fn update(&mut self, event: &WindowEvent) {
+        match *event {
+            WindowEvent::Resized(size) => self.inner_size = size,
+            WindowEvent::Destroyed => self.destroyed = true,
+            _ => {}
+        }
+    }
+}
+
+/// Events waiting for `poll_event`, along with the input state they add up to.
+struct EventQueue {
+    events: VecDeque<Event>,
+    cursor_position: [f64; 2],
+    pressed_buttons: BTreeSet<Button>,
+    active_touches: BTreeMap<(i64, i64), TouchInfo>,
+    frame_scroll: [f64; 2],
+    frame_text: String,
+    last_input: Instant,
+}
 
-            Left => Key::Left,
-            Up => Key::Up,
-            Right => Key::Right,
-            Down => Key::Down,
+impl EventQueue {
+    fn new() -> Self {
+        EventQueue {
+            events: VecDeque::new(),
+            cursor_position: [0.0, 0.0],
+            pressed_buttons: BTreeSet::new(),
+            active_touches: BTreeMap::new(),
+            frame_scroll: [0.0, 0.0],
+            frame_text: String::new(),
+            last_input: Instant::now(),
+        }
+    }
 
-            Back => Key::Backspace,
-            Return => Key::Return,
-            Space => Key::Space,
+    /// Queues an event, updating the tracked input state.
+    fn push(&mut self, event: Event) {
+        self.track(&event);
+        self.events.push_back(event);
+    }
 
-            LAlt => Key::LAlt,
-            LControl => Key::LCtrl,
-            LWin => Key::Menu,
-            LShift => Key::LShift,
+    fn len(&self) -> usize {
+        self.events.len()
+    }
 
-            RAlt => Key::LAlt,
-            RControl => Key::RCtrl,
-            RWin => Key::Menu,
-            RShift => Key::RShift,
+    /// Updates the state reported by `input_snapshot` from an event about to be pushed.
+    fn track(&mut self, event: &Event) {
+        if is_user_input(event) {
+            self.last_input = Instant::now();
+        }
 
-            Tab => Key::Tab,
-            _ => Key::Unknown,
+        match *event {
+            Event::Input(Input::Button(ButtonArgs { state, button, .. }), _) => match state {
+                ButtonState::Press => {
+                    self.pressed_buttons.insert(button);
+                }
+                ButtonState::Release => {
+                    self.pressed_buttons.remove(&button);
+                }
+            },
+            Event::Input(Input::Move(Motion::MouseCursor(position)), _) => {
+                self.cursor_position = position;
+            }
+            Event::Input(Input::Move(Motion::Touch(args)), _) => match args.touch {
+                Touch::Start | Touch::Move => {
+                    let touch = TouchInfo {
+                        device: args.device,
+                        id: args.id,
+                        position: args.position(),
+                        pressure: args.pressure(),
+                    };
+                    self.active_touches.insert((args.device, args.id), touch);
+                }
+                Touch::End | Touch::Cancel => {
+                    self.active_touches.remove(&(args.device, args.id));
+                }
+            },
+            Event::Input(Input::Move(Motion::MouseScroll([x, y])), _) => {
+                self.frame_scroll[0] += x;
+                self.frame_scroll[1] += y;
+            }
+            Event::Input(Input::Text(ref text), _) => self.frame_text.push_str(text),
+            _ => {}
         }
-    } else {
-        Key::Unknown
     }
 }
 
-fn map_keyboard_input(input: &KeyboardInput) -> Event {
-    let key = map_key(input);
+/// Holds back key presses that produce text, for the `suppress_button_for_text` option.
+///
+/// The text event arrives right after the press that produced it, so a press is held until the
+///  next event shows whether it typed anything.
+#[derive(Default)]
+struct TextKeys {
+    held_press: Option<Event>,
+    text_scancodes: HashSet<i32>,
+}
 
-    let state = if input.state == ElementState::Pressed {
-        ButtonState::Press
-    } else {
-        ButtonState::Release
-    };
+impl TextKeys {
+    /// Takes the next event, returning the events to deliver in its place.
+    fn filter(&mut self, ev: Event) -> Vec<Event> {
+        let mut events = Vec::new();
+        match ev {
+            Event::Input(
+                Input::Button(ButtonArgs {
+                    state: ButtonState::Press,
+                    button: Button::Keyboard(_),
+                    ..
+                }),
+                _,
+            ) => {
+                events.extend(self.flush());
+                self.held_press = Some(ev);
+            }
+            Event::Input(
+                Input::Button(ButtonArgs {
+                    state: ButtonState::Release,
+                    button: Button::Keyboard(_),
+                    scancode: Some(scancode),
+                }),
+                _,
+            ) if self.text_scancodes.remove(&scancode) => {}
+            Event::Input(Input::Text(_), _) => {
+                // The text replaces the press that produced it, and its release
+                if let Some(Event::Input(Input::Button(args), _)) = self.held_press.take() {
+                    if let Some(scancode) = args.scancode {
+                        self.text_scancodes.insert(scancode);
+                    }
+                }
+                events.push(ev);
+            }
+            _ => {
+                events.extend(self.flush());
+                events.push(ev);
+            }
+        }
+        events
+    }
 
-    Event::Input(
-        Input::Button(ButtonArgs {
-            state: state,
-            button: Button::Keyboard(key),
-            scancode: Some(input.scancode as i32),
-        }),
-        None,
-    )
+    /// Gives back the held press, once it's clear it typed nothing.
+    fn flush(&mut self) -> Option<Event> {
+        self.held_press.take()
+    }
 }
 
-fn map_mouse_button(button: WinitMouseButton) -> MouseButton {
-    match button {
-        WinitMouseButton::Left => MouseButton::Left,
-        WinitMouseButton::Right => MouseButton::Right,
-        WinitMouseButton::Middle => MouseButton::Middle,
-        WinitMouseButton::Other(4) => MouseButton::X1,
-        WinitMouseButton::Other(5) => MouseButton::X2,
-        WinitMouseButton::Other(6) => MouseButton::Button6,
-        WinitMouseButton::Other(7) => MouseButton::Button7,
-        WinitMouseButton::Other(8) => MouseButton::Button8,
-        _ => MouseButton::Unknown,
+/// The latest resize event held back by `set_resize_debounce`, with the time it arrived.
+#[derive(Default)]
+struct PendingResize {
+    resize: Option<(Event, Instant)>,
+}
+
+impl PendingResize {
+    /// Holds `resize`, replacing any resize from earlier in the burst.
+    fn hold(&mut self, resize: Event, now: Instant) {
+        self.resize = Some((resize, now));
+    }
+
+    /// Gives back the held resize once no other resize has arrived for `debounce`.
+    fn take_settled(&mut self, debounce: Duration, now: Instant) -> Option<Event> {
+        match self.resize {
+            Some((_, resized_at)) if now.duration_since(resized_at) >= debounce => self.take(),
+            _ => None,
+        }
+    }
+
+    fn take(&mut self) -> Option<Event> {
+        self.resize.take().map(|(resize, _)| resize)
+    }
+}
+
+/// Counts the repeats of the most recently pressed key, see `key_repeat_count`.
+#[derive(Default)]
+struct KeyRepeat {
+    scancode: Option<u32>,
+    count: u32,
+}
+
+impl KeyRepeat {
+    fn update(&mut self, state: ElementState, scancode: u32) {
+        // winit doesn't flag repeats, but a press without a release in between is one
+        match state {
+            ElementState::Pressed if self.scancode == Some(scancode) => self.count += 1,
+            ElementState::Pressed => {
+                self.scancode = Some(scancode);
+                self.count = 0;
+            }
+            ElementState::Released if self.scancode == Some(scancode) => {
+                *self = KeyRepeat::default();
+            }
+            ElementState::Released => {}
+        }
+    }
+}
+
+/// Returns button events for the modifier keys that don't match `modifiers`, given the buttons
+///  currently `pressed`.
+///
+/// Some layouts don't report the modifier keys themselves reliably. winit doesn't say which
+///  side changed, so presses are reported as the left key, and releases for whichever sides
+///  are down. Modifiers in `seen`, whose keys sent real key events this pump, are left alone.
+///  Those events already tell the story, and on Windows arrive after the `ModifiersChanged`.
+fn modifier_key_events(
+    pressed: &BTreeSet<Button>,
+    modifiers: ModifiersState,
+    seen: ModifiersState,
+) -> Vec<Event> {
+    let keys = [
+        (ModifiersState::SHIFT, Key::LShift, Key::RShift),
+        (ModifiersState::CTRL, Key::LCtrl, Key::RCtrl),
+        (ModifiersState::ALT, Key::LAlt, Key::RAlt),
+    ];
+    let mut events = Vec::new();
+    for &(modifier, left, right) in keys.iter() {
+        if seen.contains(modifier) {
+            continue;
+        }
+        let held = modifiers.contains(modifier);
+
+        let down: Vec<Key> = [left, right]
+            .iter()
+            .cloned()
+            .filter(|&key| pressed.contains(&Button::Keyboard(key)))
+            .collect();
+        let (state, keys) = match (held, down.is_empty()) {
+            (true, true) => (ButtonState::Press, vec![left]),
+            (false, false) => (ButtonState::Release, down),
+            _ => continue,
+        };
+
+        for key in keys {
+            events.push(Event::Input(
+                Input::Button(ButtonArgs {
+                    state,
+                    button: Button::Keyboard(key),
+                    scancode: None,
+                }),
+                None,
+            ));
+        }
+    }
+    events
+}
+
+/// Collects the statistics of a pump that received `events`, see `WinitWindow::last_pump_stats`.
+fn pump_stats(events: &[winit::event::Event<UserEvent>], polled: bool) -> PumpStats {
+    PumpStats {
+        events_processed: events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    winit::event::Event::WindowEvent { .. }
+                        | winit::event::Event::DeviceEvent { .. }
+                        | winit::event::Event::RedrawRequested(_)
+                )
+            })
+            .count(),
+        timed_out: false,
+        polled,
     }
 }
 
 /// Converts a winit's [`WindowEvent`] into a piston's [`Event`].
 ///
+/// `draw_size` is the current inner size of the window, used to normalize touch positions.
+///
 /// For some events that will not be passed to the user, returns `None`.
-fn map_window_event(window_evnet: WindowEvent) -> Option<Event> {
+fn map_window_event(window_evnet: WindowEvent, draw_size: PhysicalSize<u32>) -> Option<Event> {
     match window_evnet {
         // TODO: This event needs to be added to pistoncore-input, see issue
         //  PistonDevelopers/piston#1117
@@ -456,16 +3081,15 @@ fn map_window_event(window_evnet: WindowEvent) -> Option<Event> {
             }),
             None,
         )),
-        // TODO: Implement this
+        // Tracked by `handle_event` for `get_position` and monitor changes
         WindowEvent::Moved(_) => None,
         WindowEvent::CloseRequested => Some(Event::Input(Input::Close(CloseArgs), None)),
-        // TODO: Implement this
-        WindowEvent::Destroyed => None,
-        // TODO: Implement this
+        WindowEvent::Destroyed => Some(Event::Input(Input::Close(CloseArgs), None)),
+        // Batched by `handle_event` for the file drop handler
         WindowEvent::DroppedFile(_) => None,
         // TODO: Implement this
         WindowEvent::HoveredFile(_) => None,
-        // TODO: Implement this
+        // Clears the batch `handle_event` is collecting for the file drop handler
         WindowEvent::HoveredFileCancelled => None,
         WindowEvent::ReceivedCharacter(c) => match c {
             // Ignore control characters
@@ -477,7 +3101,7 @@ fn map_window_event(window_evnet: WindowEvent) -> Option<Event> {
         },
         WindowEvent::Focused(focused) => Some(Event::Input(Input::Focus(focused), None)),
         WindowEvent::KeyboardInput { input, .. } => Some(map_keyboard_input(&input)),
-        // TODO: Implement this
+        // Tracked by `handle_event`, which reports modifier keys that send no key events
         WindowEvent::ModifiersChanged(_) => None,
         WindowEvent::CursorMoved { position, .. } => Some(Event::Input(
             Input::Move(Motion::MouseCursor([position.x, position.y])),
@@ -487,7 +3111,7 @@ fn map_window_event(window_evnet: WindowEvent) -> Option<Event> {
         WindowEvent::CursorLeft { .. } => Some(Event::Input(Input::Cursor(false), None)),
         WindowEvent::MouseWheel { delta, .. } => Some(match delta {
             MouseScrollDelta::PixelDelta(PhysicalPosition { x, y }) => {
-                Event::Input(Input::Move(Motion::MouseScroll([x as f64, y as f64])), None)
+                Event::Input(Input::Move(Motion::MouseScroll([x, y])), None)
             }
             MouseScrollDelta::LineDelta(x, y) => {
                 Event::Input(Input::Move(Motion::MouseScroll([x as f64, y as f64])), None)
@@ -510,21 +3134,743 @@ fn map_window_event(window_evnet: WindowEvent) -> Option<Event> {
             )
         }),
         // TODO: Implement this
-        WindowEvent::TouchpadPressure { .. } |
-        WindowEvent::TouchpadMagnify { .. } |
-        WindowEvent::SmartMagnify { .. } |
-        WindowEvent::TouchpadRotate { .. } => None,
+        WindowEvent::TouchpadPressure { .. }
+        | WindowEvent::TouchpadMagnify { .. }
+        | WindowEvent::SmartMagnify { .. }
+        | WindowEvent::TouchpadRotate { .. } => None,
         // TODO: Implement this
         WindowEvent::AxisMotion { .. } => None,
-        // TODO: Implement this
-        WindowEvent::Touch(_) => None,
-        // TODO: Implement this
+        WindowEvent::Touch(touch) => Some(map_touch(touch, draw_size)),
+        // `pump_events` turns these into resizes before they get here
         WindowEvent::ScaleFactorChanged { .. } => None,
-        // TODO: Implement this
         WindowEvent::ThemeChanged(_) => None,
-        // TODO: Implement this
+        // Composition is tracked by `WinitWindow`, only the final text is input
+        WindowEvent::Ime(Ime::Commit(text)) => Some(Event::Input(Input::Text(text), None)),
         WindowEvent::Ime(_) => None,
-        // TODO: Implement this
+        // Tracked by `handle_event`, which holds back render events while occluded
         WindowEvent::Occluded(_) => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::event::{DeviceId, Force};
+
+    fn filter(event: WindowEvent, options: &InputOptions) -> Option<Event> {
+        filter_window_event(
+            event,
+            options,
+            ModifiersState::empty(),
+            1.0,
+            PhysicalSize::new(800, 600),
+        )
+    }
+
+    #[allow(deprecated)]
+    fn scroll(x: f32, y: f32) -> WindowEvent<'static> {
+        WindowEvent::MouseWheel {
+            device_id: unsafe { DeviceId::dummy() },
+            delta: MouseScrollDelta::LineDelta(x, y),
+            phase: TouchPhase::Moved,
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    #[allow(deprecated)]
+    fn cursor_moved(x: f64, y: f64) -> WindowEvent<'static> {
+        WindowEvent::CursorMoved {
+            device_id: unsafe { DeviceId::dummy() },
+            position: PhysicalPosition::new(x, y),
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    fn cursor_position(event: Option<Event>) -> [f64; 2] {
+        match event {
+            Some(Event::Input(Input::Move(Motion::MouseCursor(position)), _)) => position,
+            _ => panic!("expected a cursor event"),
+        }
+    }
+
+    fn scroll_delta(event: Option<Event>) -> [f64; 2] {
+        match event {
+            Some(Event::Input(Input::Move(Motion::MouseScroll(delta)), _)) => delta,
+            _ => panic!("expected a scroll event"),
+        }
+    }
+
+    fn text(event: Option<Event>) -> String {
+        match event {
+            Some(Event::Input(Input::Text(text), _)) => text,
+            _ => panic!("expected a text event"),
+        }
+    }
+
+    fn key(state: ButtonState, key: Key, scancode: i32) -> Event {
+        Event::Input(
+            Input::Button(ButtonArgs {
+                state,
+                button: Button::Keyboard(key),
+                scancode: Some(scancode),
+            }),
+            None,
+        )
+    }
+
+    fn inputs(events: Vec<Event>) -> Vec<Input> {
+        events
+            .into_iter()
+            .map(|event| match event {
+                Event::Input(input, _) => input,
+                _ => panic!("expected an input event"),
+            })
+            .collect()
+    }
+
+    fn resize(width: f64) -> Event {
+        Event::Input(
+            Input::Resize(ResizeArgs {
+                window_size: [width, 100.0],
+                draw_size: [width as u32, 100],
+            }),
+            None,
+        )
+    }
+
+    #[allow(deprecated)]
+    fn keyboard_input(virtual_keycode: VirtualKeyCode) -> KeyboardInput {
+        KeyboardInput {
+            scancode: 0,
+            state: ElementState::Pressed,
+            virtual_keycode: Some(virtual_keycode),
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    fn touch(x: f64, y: f64, force: Option<Force>) -> WinitTouch {
+        WinitTouch {
+            // Never handed back to winit, only hashed
+            device_id: unsafe { DeviceId::dummy() },
+            phase: TouchPhase::Started,
+            location: PhysicalPosition::new(x, y),
+            force,
+            id: 7,
+        }
+    }
+
+    fn touch_args(event: Event) -> TouchArgs {
+        match event {
+            Event::Input(Input::Move(Motion::Touch(args)), _) => args,
+            _ => panic!("expected a touch event"),
+        }
+    }
+
+    #[allow(deprecated)]
+    fn mouse_input(button: WinitMouseButton) -> WindowEvent<'static> {
+        WindowEvent::MouseInput {
+            device_id: unsafe { DeviceId::dummy() },
+            state: ElementState::Pressed,
+            button,
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    #[test]
+    fn scroll_dead_zone() {
+        let options = InputOptions {
+            scroll_dead_zone: 0.5,
+            ..InputOptions::default()
+        };
+        assert!(filter(scroll(0.3, 0.3), &options).is_none());
+        assert_eq!(scroll_delta(filter(scroll(0.0, 1.0), &options)), [0.0, 1.0]);
+    }
+
+    #[test]
+    fn ignore_unknown_mouse_buttons() {
+        let mut options = InputOptions::default();
+        assert!(filter(mouse_input(WinitMouseButton::Other(99)), &options).is_some());
+        options.ignore_unknown_mouse_buttons = true;
+        assert!(filter(mouse_input(WinitMouseButton::Other(99)), &options).is_none());
+        assert!(filter(mouse_input(WinitMouseButton::Other(4)), &options).is_some());
+    }
+
+    #[test]
+    fn viewport_offsets_and_clamps_cursor() {
+        let options = InputOptions {
+            viewport: Some((Position { x: 100, y: 50 }, Size::from([200.0, 100.0]))),
+            ..InputOptions::default()
+        };
+        assert_eq!(
+            cursor_position(filter(cursor_moved(150.0, 70.0), &options)),
+            [50.0, 20.0]
+        );
+        assert_eq!(
+            cursor_position(filter(cursor_moved(10.0, 500.0), &options)),
+            [0.0, 100.0]
+        );
+    }
+
+    #[test]
+    fn flip_y_measures_from_the_bottom() {
+        let mut options = InputOptions {
+            flip_y: true,
+            ..InputOptions::default()
+        };
+        assert_eq!(
+            cursor_position(filter(cursor_moved(10.0, 100.0), &options)),
+            [10.0, 500.0]
+        );
+        options.viewport = Some((Position { x: 0, y: 0 }, Size::from([400.0, 300.0])));
+        assert_eq!(
+            cursor_position(filter(cursor_moved(10.0, 100.0), &options)),
+            [10.0, 200.0]
+        );
+    }
+
+    #[test]
+    fn shift_scrolls_horizontal() {
+        let options = InputOptions {
+            shift_scrolls_horizontal: true,
+            ..InputOptions::default()
+        };
+        let shifted = |event| {
+            filter_window_event(
+                event,
+                &options,
+                ModifiersState::SHIFT,
+                1.0,
+                PhysicalSize::new(800, 600),
+            )
+        };
+        assert_eq!(scroll_delta(shifted(scroll(0.0, 2.0))), [2.0, 0.0]);
+        assert_eq!(scroll_delta(filter(scroll(0.0, 2.0), &options)), [0.0, 2.0]);
+    }
+
+    #[test]
+    fn control_chars_as_text() {
+        let mut options = InputOptions::default();
+        assert!(filter(WindowEvent::ReceivedCharacter('\r'), &options).is_none());
+        options.control_chars_as_text = true;
+        assert_eq!(
+            text(filter(WindowEvent::ReceivedCharacter('\r'), &options)),
+            "\n"
+        );
+        assert_eq!(
+            text(filter(WindowEvent::ReceivedCharacter('\n'), &options)),
+            "\n"
+        );
+        assert_eq!(
+            text(filter(WindowEvent::ReceivedCharacter('\t'), &options)),
+            "\t"
+        );
+        assert!(filter(WindowEvent::ReceivedCharacter('\u{1b}'), &options).is_none());
+    }
+
+    #[test]
+    fn input_disabled_keeps_window_events() {
+        let options = InputOptions {
+            input_enabled: false,
+            ..InputOptions::default()
+        };
+        assert!(filter(mouse_input(WinitMouseButton::Left), &options).is_none());
+        assert!(filter(cursor_moved(1.0, 2.0), &options).is_none());
+        assert!(filter(WindowEvent::ReceivedCharacter('a'), &options).is_none());
+        assert!(filter(WindowEvent::CloseRequested, &options).is_some());
+        assert!(filter(WindowEvent::Resized(PhysicalSize::new(10, 10)), &options).is_some());
+        assert!(filter(WindowEvent::Focused(true), &options).is_some());
+        let entered = WindowEvent::CursorEntered {
+            device_id: unsafe { DeviceId::dummy() },
+        };
+        assert!(filter(entered, &options).is_some());
+    }
+
+    #[test]
+    fn text_keys_report_only_text() {
+        let mut text_keys = TextKeys::default();
+        assert!(text_keys
+            .filter(key(ButtonState::Press, Key::A, 30))
+            .is_empty());
+        let typed = text_keys.filter(Event::Input(Input::Text("a".into()), None));
+        assert_eq!(inputs(typed), [Input::Text("a".into())]);
+        assert!(text_keys
+            .filter(key(ButtonState::Release, Key::A, 30))
+            .is_empty());
+
+        // A press that types nothing is given back by whatever comes next
+        assert!(text_keys
+            .filter(key(ButtonState::Press, Key::Left, 105))
+            .is_empty());
+        let release = text_keys.filter(key(ButtonState::Release, Key::Left, 105));
+        assert_eq!(
+            inputs(release),
+            inputs(vec![
+                key(ButtonState::Press, Key::Left, 105),
+                key(ButtonState::Release, Key::Left, 105),
+            ])
+        );
+        assert!(text_keys.flush().is_none());
+    }
+
+    #[test]
+    fn pending_resize_keeps_last_of_burst() {
+        let debounce = Duration::from_millis(50);
+        let start = Instant::now();
+        let mut pending = PendingResize::default();
+        pending.hold(resize(10.0), start);
+        pending.hold(resize(20.0), start + Duration::from_millis(30));
+        let settling = start + Duration::from_millis(60);
+        assert!(pending.take_settled(debounce, settling).is_none());
+        let settled = start + Duration::from_millis(80);
+        assert_eq!(
+            inputs(
+                pending
+                    .take_settled(debounce, settled)
+                    .into_iter()
+                    .collect()
+            ),
+            inputs(vec![resize(20.0)])
+        );
+        assert!(pending.take().is_none());
+    }
+
+    #[test]
+    fn key_repeat_counts_presses_without_release() {
+        let mut repeat = KeyRepeat::default();
+        repeat.update(ElementState::Pressed, 30);
+        assert_eq!(repeat.count, 0);
+        repeat.update(ElementState::Pressed, 30);
+        repeat.update(ElementState::Pressed, 30);
+        assert_eq!(repeat.count, 2);
+
+        // Releasing another key doesn't end the repeat, pressing one starts over
+        repeat.update(ElementState::Released, 31);
+        assert_eq!(repeat.count, 2);
+        repeat.update(ElementState::Pressed, 31);
+        assert_eq!(repeat.count, 0);
+        repeat.update(ElementState::Pressed, 31);
+        repeat.update(ElementState::Released, 31);
+        assert_eq!(repeat.count, 0);
+    }
+
+    #[test]
+    fn modifier_key_events_diff_pressed_keys() {
+        let modifier_key = |state, key| {
+            Input::Button(ButtonArgs {
+                state,
+                button: Button::Keyboard(key),
+                scancode: None,
+            })
+        };
+        let mut pressed = BTreeSet::new();
+        pressed.insert(Button::Keyboard(Key::LCtrl));
+        pressed.insert(Button::Keyboard(Key::RCtrl));
+
+        let events = modifier_key_events(&pressed, ModifiersState::SHIFT, ModifiersState::empty());
+        assert_eq!(
+            inputs(events),
+            [
+                modifier_key(ButtonState::Press, Key::LShift),
+                modifier_key(ButtonState::Release, Key::LCtrl),
+                modifier_key(ButtonState::Release, Key::RCtrl),
+            ]
+        );
+
+        // Keys that sent their own events are left alone
+        let events = modifier_key_events(&pressed, ModifiersState::SHIFT, ModifiersState::all());
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn event_queue_counts_pending_events() {
+        let mut queue = EventQueue::new();
+        for _ in 0..5 {
+            queue.push(Event::Input(Input::Close(CloseArgs), None));
+        }
+        assert_eq!(queue.len(), 5);
+        queue.events.pop_front();
+        assert_eq!(queue.len(), 4);
+    }
+
+    #[test]
+    fn pump_stats_count_window_and_device_events() {
+        use winit::event::{DeviceEvent, StartCause};
+        use winit::window::WindowId;
+
+        let window_id = unsafe { WindowId::dummy() };
+        let events = vec![
+            winit::event::Event::NewEvents(StartCause::Poll),
+            winit::event::Event::WindowEvent {
+                window_id,
+                event: WindowEvent::Focused(true),
+            },
+            winit::event::Event::WindowEvent {
+                window_id,
+                event: WindowEvent::CloseRequested,
+            },
+            winit::event::Event::DeviceEvent {
+                device_id: unsafe { DeviceId::dummy() },
+                event: DeviceEvent::Added,
+            },
+            winit::event::Event::MainEventsCleared,
+            winit::event::Event::RedrawRequested(window_id),
+            winit::event::Event::RedrawEventsCleared,
+        ];
+        assert_eq!(
+            pump_stats(&events, true),
+            PumpStats {
+                events_processed: 4,
+                timed_out: false,
+                polled: true,
+            }
+        );
+        assert!(!pump_stats(&[], false).polled);
+    }
+
+    #[test]
+    fn window_cache_keeps_size_after_destroy() {
+        let mut cache = WindowCache::new(PhysicalSize::new(800, 600));
+        cache.update(&WindowEvent::Resized(PhysicalSize::new(1024, 768)));
+        assert!(!cache.destroyed);
+        cache.update(&WindowEvent::Destroyed);
+        assert!(cache.destroyed);
+        assert_eq!(cache.inner_size, PhysicalSize::new(1024, 768));
+    }
+
+    #[test]
+    fn map_window_event_destroyed() {
+        match map_window_event(WindowEvent::Destroyed, PhysicalSize::new(800, 600)) {
+            Some(Event::Input(Input::Close(_), _)) => {}
+            _ => panic!("expected a close event"),
+        }
+    }
+
+    #[test]
+    fn map_key_numpad() {
+        let keys = [
+            (VirtualKeyCode::Numpad0, Key::NumPad0),
+            (VirtualKeyCode::Numpad5, Key::NumPad5),
+            (VirtualKeyCode::Numpad9, Key::NumPad9),
+            (VirtualKeyCode::NumpadAdd, Key::NumPadPlus),
+            (VirtualKeyCode::NumpadSubtract, Key::NumPadMinus),
+            (VirtualKeyCode::NumpadMultiply, Key::NumPadMultiply),
+            (VirtualKeyCode::NumpadDivide, Key::NumPadDivide),
+            (VirtualKeyCode::NumpadDecimal, Key::NumPadPeriod),
+            (VirtualKeyCode::NumpadEnter, Key::NumPadEnter),
+        ];
+        for &(virtual_keycode, key) in keys.iter() {
+            assert_eq!(map_key(&keyboard_input(virtual_keycode)), key);
+        }
+    }
+
+    #[test]
+    fn map_key_navigation() {
+        let keys = [
+            (VirtualKeyCode::Insert, Key::Insert),
+            (VirtualKeyCode::Delete, Key::Delete),
+            (VirtualKeyCode::Home, Key::Home),
+            (VirtualKeyCode::End, Key::End),
+            (VirtualKeyCode::PageUp, Key::PageUp),
+            (VirtualKeyCode::PageDown, Key::PageDown),
+            (VirtualKeyCode::Left, Key::Left),
+            (VirtualKeyCode::Up, Key::Up),
+            (VirtualKeyCode::Right, Key::Right),
+            (VirtualKeyCode::Down, Key::Down),
+        ];
+        for &(virtual_keycode, key) in keys.iter() {
+            assert_eq!(map_key(&keyboard_input(virtual_keycode)), key);
+        }
+    }
+
+    #[test]
+    fn map_key_locks() {
+        let keys = [
+            (VirtualKeyCode::Capital, Key::CapsLock),
+            (VirtualKeyCode::Numlock, Key::NumLockClear),
+            (VirtualKeyCode::Scroll, Key::ScrollLock),
+        ];
+        for &(virtual_keycode, key) in keys.iter() {
+            assert_eq!(map_key(&keyboard_input(virtual_keycode)), key);
+        }
+    }
+
+    #[test]
+    fn map_cursor_keeps_names() {
+        let cursors = [
+            CursorIcon::Text,
+            CursorIcon::VerticalText,
+            CursorIcon::Crosshair,
+            CursorIcon::Hand,
+            CursorIcon::Help,
+            CursorIcon::Wait,
+            CursorIcon::Progress,
+            CursorIcon::NotAllowed,
+            CursorIcon::ContextMenu,
+            CursorIcon::Cell,
+            CursorIcon::Alias,
+            CursorIcon::Copy,
+            CursorIcon::NoDrop,
+            CursorIcon::Move,
+            CursorIcon::Grab,
+            CursorIcon::Grabbing,
+            CursorIcon::AllScroll,
+            CursorIcon::ZoomIn,
+            CursorIcon::ZoomOut,
+            CursorIcon::EResize,
+            CursorIcon::NResize,
+            CursorIcon::NeResize,
+            CursorIcon::NwResize,
+            CursorIcon::SResize,
+            CursorIcon::SeResize,
+            CursorIcon::SwResize,
+            CursorIcon::WResize,
+            CursorIcon::EwResize,
+            CursorIcon::NsResize,
+            CursorIcon::NeswResize,
+            CursorIcon::NwseResize,
+            CursorIcon::ColResize,
+            CursorIcon::RowResize,
+        ];
+        // Apart from the arrow, every cursor has the same name in winit
+        assert_eq!(map_cursor(CursorIcon::Arrow), WinitCursorIcon::Default);
+        for &cursor in cursors.iter() {
+            assert_eq!(format!("{:?}", map_cursor(cursor)), format!("{:?}", cursor));
+        }
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+    fn map_scancode_main_block() {
+        assert_eq!(map_scancode(1), Key::Escape);
+        assert_eq!(map_scancode(16), Key::Q);
+        assert_eq!(map_scancode(30), Key::A);
+        assert_eq!(map_scancode(57), Key::Space);
+        assert_eq!(map_scancode(79), Key::NumPad1);
+        assert_eq!(map_scancode(88), Key::F12);
+        assert_eq!(map_scancode(0), Key::Unknown);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    fn map_scancode_macos() {
+        assert_eq!(map_scancode(30), Key::Unknown);
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+    fn map_sdl_scancode_main_block() {
+        assert_eq!(map_sdl_scancode(30), Some(4));
+        assert_eq!(map_sdl_scancode(11), Some(39));
+        assert_eq!(map_sdl_scancode(28), Some(40));
+        assert_eq!(map_sdl_scancode(42), Some(225));
+        assert_eq!(map_sdl_scancode(82), Some(98));
+        assert_eq!(map_sdl_scancode(0), None);
+    }
+
+    #[test]
+    #[cfg(not(any(windows, target_os = "macos", target_os = "ios")))]
+    fn map_sdl_scancode_extended() {
+        let scancodes = [
+            (103, 82),
+            (105, 80),
+            (106, 79),
+            (108, 81),
+            (110, 73),
+            (102, 74),
+            (107, 77),
+            (104, 75),
+            (109, 78),
+            (111, 76),
+            (97, 228),
+            (100, 230),
+            (125, 227),
+            (126, 231),
+            (96, 88),
+            (98, 84),
+            (183, 104),
+            (185, 106),
+        ];
+        for &(scancode, sdl_scancode) in scancodes.iter() {
+            assert_eq!(map_sdl_scancode(scancode), Some(sdl_scancode));
+        }
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn map_sdl_scancode_extended() {
+        let scancodes = [
+            (0xE048, 82),
+            (0xE04B, 80),
+            (0xE04D, 79),
+            (0xE050, 81),
+            (0xE01D, 228),
+            (0xE038, 230),
+            (0xE05B, 227),
+            (0x64, 104),
+        ];
+        for &(scancode, sdl_scancode) in scancodes.iter() {
+            assert_eq!(map_sdl_scancode(scancode), Some(sdl_scancode));
+        }
+    }
+
+    #[test]
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    fn map_sdl_scancode_macos() {
+        assert_eq!(map_sdl_scancode(30), None);
+    }
+
+    #[test]
+    fn map_touch_normalizes() {
+        let args = touch_args(map_touch(
+            touch(50.0, 75.0, None),
+            PhysicalSize::new(200, 100),
+        ));
+        assert_eq!(args.position(), [0.25, 0.75]);
+        assert_eq!(args.id, 7);
+        assert_eq!(args.touch, Touch::Start);
+        // No force reported counts as full pressure
+        assert_eq!(args.pressure(), 1.0);
+    }
+    #[test]
+    fn map_touch_zero_size() {
+        let args = touch_args(map_touch(
+            touch(50.0, 75.0, Some(Force::Normalized(0.5))),
+            PhysicalSize::new(0, 0),
+        ));
+        assert_eq!(args.position(), [0.0, 0.0]);
+        assert_eq!(args.pressure(), 0.5);
+    }
+
+    #[test]
+    fn map_window_event_touch_sequence() {
+        let draw_size = PhysicalSize::new(200, 100);
+        let touches = [
+            (TouchPhase::Started, 7, [20.0, 10.0], Touch::Start),
+            (TouchPhase::Started, 8, [100.0, 50.0], Touch::Start),
+            (TouchPhase::Moved, 7, [40.0, 20.0], Touch::Move),
+            (TouchPhase::Ended, 7, [40.0, 20.0], Touch::End),
+            (TouchPhase::Cancelled, 8, [100.0, 50.0], Touch::Cancel),
+        ];
+        for &(phase, id, [x, y], expected) in touches.iter() {
+            let event = WindowEvent::Touch(WinitTouch {
+                phase,
+                id,
+                ..touch(x, y, None)
+            });
+            let args = touch_args(map_window_event(event, draw_size).unwrap());
+            assert_eq!(args.touch, expected);
+            assert_eq!(args.id, id as i64);
+            assert_eq!(args.position(), [x / 200.0, y / 100.0]);
+        }
+    }
+
+    #[test]
+    fn map_window_event_ime_commit() {
+        let event = WindowEvent::Ime(Ime::Commit("日本".to_string()));
+        match map_window_event(event, PhysicalSize::new(100, 100)) {
+            Some(Event::Input(Input::Text(text), _)) => assert_eq!(text, "日本"),
+            _ => panic!("expected a text event"),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ui-input")]
+    fn ui_input_from_snapshot() {
+        let snapshot = InputSnapshot {
+            cursor: [10.0, 20.0],
+            pressed: vec![
+                Button::Mouse(MouseButton::Left),
+                Button::Mouse(MouseButton::Middle),
+                Button::Mouse(MouseButton::X1),
+                Button::Keyboard(Key::A),
+            ],
+            scroll: [0.0, -1.0],
+            text: "a".to_string(),
+            modifiers: Modifiers {
+                shift: true,
+                ..Modifiers::default()
+            },
+        };
+        let ui = UiInput::from(snapshot);
+        assert_eq!(ui.mouse_position, [10.0, 20.0]);
+        assert_eq!(ui.mouse_down, [true, false, true]);
+        assert_eq!(ui.scroll, [0.0, -1.0]);
+        assert_eq!(ui.text, "a");
+        assert_eq!(ui.keys_down, vec![Key::A]);
+        assert!(ui.modifiers.shift);
+    }
+
+    #[test]
+    fn user_input() {
+        let press = key(ButtonState::Press, Key::A, 30);
+        assert!(is_user_input(&press));
+        assert!(is_user_input(&Event::Input(Input::Text("a".into()), None)));
+        assert!(is_user_input(&Event::Input(
+            Input::Move(Motion::MouseScroll([0.0, 1.0])),
+            None
+        )));
+        assert!(!is_user_input(&Event::Input(Input::Focus(true), None)));
+        assert!(!is_user_input(&Event::Input(Input::Cursor(true), None)));
+        assert!(!is_user_input(&resize(100.0)));
+        assert!(!is_user_input(&Event::Input(Input::Close(CloseArgs), None)));
+        assert!(!is_user_input(&Event::Custom(
+            THEME_CHANGED,
+            Arc::new(Theme::Dark),
+            None
+        )));
+    }
+
+    #[test]
+    fn map_window_level_keeps_names() {
+        let levels = [
+            (WindowLevel::Normal, winit::window::WindowLevel::Normal),
+            (
+                WindowLevel::AlwaysOnTop,
+                winit::window::WindowLevel::AlwaysOnTop,
+            ),
+            (
+                WindowLevel::AlwaysOnBottom,
+                winit::window::WindowLevel::AlwaysOnBottom,
+            ),
+        ];
+        for &(level, expected) in levels.iter() {
+            assert_eq!(map_window_level(level), expected);
+        }
+    }
+
+    #[test]
+    fn map_ime_purpose_keeps_names() {
+        let purposes = [
+            (ImePurpose::Normal, winit::window::ImePurpose::Normal),
+            (ImePurpose::Password, winit::window::ImePurpose::Password),
+            (ImePurpose::Terminal, winit::window::ImePurpose::Terminal),
+        ];
+        for &(purpose, expected) in purposes.iter() {
+            assert_eq!(map_ime_purpose(purpose), expected);
+        }
+    }
+
+    #[test]
+    fn check_icon_size_errors() {
+        match check_icon_size(&[0; 6], 1, 1) {
+            Err(BadIcon::ByteCountNotDivisibleBy4 { byte_count: 6 }) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+        match check_icon_size(&[0; 8], 1, 1) {
+            Err(BadIcon::DimensionsVsPixelCount { pixel_count: 2, .. }) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+        // Too large to multiply in 32 bits
+        assert!(check_icon_size(&[0; 16], 0x10000, 0x10000).is_err());
+        assert!(check_icon_size(&[0; 16], 2, 2).is_ok());
+    }
+
+    #[test]
+    fn clamp_progress_range() {
+        assert_eq!(clamp_progress(Some(0.5)), Some(0.5));
+        assert_eq!(clamp_progress(Some(-1.0)), Some(0.0));
+        assert_eq!(clamp_progress(Some(2.0)), Some(1.0));
+        assert_eq!(clamp_progress(Some(f64::NAN)), None);
+        assert_eq!(clamp_progress(None), None);
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{InputSnapshot, Modifiers, WinitWindow};
+use input::{Button, Key, MouseButton};
+
+/// Per-frame input in the shape immediate-mode GUI libraries expect
+///
+/// Integrations with GUI libraries like conrod or imgui can feed this to them once per frame,
+///  rather than each mapping piston events themselves.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UiInput {
+    /// Cursor position, in the same coordinates as `Motion::MouseCursor`
+    pub mouse_position: [f64; 2],
+    /// Whether the left, right and middle mouse buttons are held, in that order
+    pub mouse_down: [bool; 3],
+    /// Scroll accumulated this frame
+    pub scroll: [f64; 2],
+    /// Text typed this frame
+    pub text: String,
+    /// Keyboard keys currently held
+    pub keys_down: Vec<Key>,
+    /// Current modifier key state
+    pub modifiers: Modifiers,
+}
+
+impl From<InputSnapshot> for UiInput {
+    fn from(snapshot: InputSnapshot) -> Self {
+        let mut mouse_down = [false; 3];
+        let mut keys_down = Vec::new();
+        for button in snapshot.pressed {
+            match button {
+                Button::Mouse(MouseButton::Left) => mouse_down[0] = true,
+                Button::Mouse(MouseButton::Right) => mouse_down[1] = true,
+                Button::Mouse(MouseButton::Middle) => mouse_down[2] = true,
+                Button::Keyboard(key) => keys_down.push(key),
+                _ => {}
+            }
+        }
+
+        UiInput {
+            mouse_position: snapshot.cursor,
+            mouse_down,
+            scroll: snapshot.scroll,
+            text: snapshot.text,
+            keys_down,
+            modifiers: snapshot.modifiers,
+        }
+    }
+}
+
+impl WinitWindow {
+    /// Returns the current input for feeding an immediate-mode GUI library.
+    ///
+    /// This is `input_snapshot` reshaped, see there for when the per-frame parts start over.
+    pub fn ui_input(&self) -> UiInput {
+        self.input_snapshot().into()
+    }
+}
@@ -102,7 +102,14 @@ impl VulkanoWindow {
                 }
 
                 // Usual events are handled here and passed to user.
-                if let Some(ev) = map_window_event(event) {
+                let draw_size = self.get_window().inner_size();
+                if let Some(mut ev) = map_window_event(event, draw_size) {
+                    // Both sizes come in physical pixels, the window size should be logical
+                    if let Event::Input(Input::Resize(ref mut args), _) = ev {
+                        let scale_factor = self.get_window().scale_factor();
+                        args.window_size[0] /= scale_factor;
+                        args.window_size[1] /= scale_factor;
+                    }
                     self.queued_events.push_back(ev);
                 }
             }
@@ -273,9 +280,10 @@ impl AdvancedWindow for VulkanoWindow {
     }
 
     fn set_position<P: Into<Position>>(&mut self, val: P) {
+        // Positions are in physical pixels, the same space `get_position` and `Moved` report in
         let val = val.into();
         self.get_window()
-            .set_outer_position(LogicalPosition::new(val.x as f64, val.y as f64))
+            .set_outer_position(PhysicalPosition::new(val.x, val.y))
     }
 
     fn set_size<S: Into<Size>>(&mut self, size: S) {
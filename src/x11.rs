@@ -0,0 +1,152 @@
+//! Window manager requests that winit doesn't make itself, sent through Xlib directly.
+
+use std::os::raw::{c_int, c_long, c_uchar, c_ulong};
+use std::ptr;
+use winit::platform::x11::WindowExtX11;
+use winit::window::Window;
+use x11_dl::xlib;
+
+/// The Xlib connection of a window on X11
+///
+/// libX11 is loaded once along with the window, rather than for every request.
+pub struct X11 {
+    xlib: xlib::Xlib,
+    display: *mut xlib::Display,
+    window: xlib::Window,
+}
+
+impl X11 {
+    /// Returns `None` when the window isn't on X11, or libX11 can't be loaded.
+    pub fn new(window: &Window) -> Option<X11> {
+        let display = window.xlib_display()? as *mut xlib::Display;
+        let window = window.xlib_window()?;
+        let xlib = xlib::Xlib::open().ok()?;
+        Some(X11 {
+            xlib,
+            display,
+            window,
+        })
+    }
+
+    /// Restarts the screen saver timer, as if the user had just used the computer.
+    pub fn reset_screen_saver(&self) {
+        unsafe {
+            (self.xlib.XResetScreenSaver)(self.display);
+            (self.xlib.XFlush)(self.display);
+        }
+    }
+
+    /// Sets `_NET_WM_WINDOW_OPACITY`, which compositing window managers apply to the window.
+    pub fn set_opacity(&self, opacity: f32) {
+        // The property is a 32-bit cardinal where fully opaque is 0xffffffff
+        let value = (opacity as f64 * u32::MAX as f64) as c_ulong;
+        unsafe {
+            let atom = (self.xlib.XInternAtom)(
+                self.display,
+                b"_NET_WM_WINDOW_OPACITY\0".as_ptr() as *const _,
+                xlib::False,
+            );
+            (self.xlib.XChangeProperty)(
+                self.display,
+                self.window,
+                atom,
+                xlib::XA_CARDINAL,
+                32,
+                xlib::PropModeReplace,
+                &value as *const c_ulong as *const c_uchar,
+                1,
+            );
+            (self.xlib.XFlush)(self.display);
+        }
+    }
+
+    /// Asks the window manager to show the window on every virtual desktop, through
+    ///  `_NET_WM_DESKTOP`, or to move it back to the current one.
+    pub fn set_visible_on_all_workspaces(&self, value: bool) {
+        unsafe {
+            let root = (self.xlib.XDefaultRootWindow)(self.display);
+            let desktop = if value {
+                0xffff_ffff_u32 as c_long
+            } else {
+                let current = (self.xlib.XInternAtom)(
+                    self.display,
+                    b"_NET_CURRENT_DESKTOP\0".as_ptr() as *const _,
+                    xlib::False,
+                );
+                let mut actual_type = 0;
+                let mut actual_format: c_int = 0;
+                let mut items: c_ulong = 0;
+                let mut remaining: c_ulong = 0;
+                let mut data: *mut c_uchar = ptr::null_mut();
+                let status = (self.xlib.XGetWindowProperty)(
+                    self.display,
+                    root,
+                    current,
+                    0,
+                    1,
+                    xlib::False,
+                    xlib::XA_CARDINAL,
+                    &mut actual_type,
+                    &mut actual_format,
+                    &mut items,
+                    &mut remaining,
+                    &mut data,
+                );
+                // Format 32 properties are returned as an array of C longs
+                let desktop = if status == xlib::Success as c_int
+                    && actual_format == 32
+                    && items == 1
+                    && !data.is_null()
+                {
+                    *(data as *const c_long)
+                } else {
+                    0
+                };
+                if !data.is_null() {
+                    (self.xlib.XFree)(data as *mut _);
+                }
+                desktop
+            };
+
+            let mut message = xlib::XClientMessageEvent {
+                type_: xlib::ClientMessage,
+                serial: 0,
+                send_event: xlib::True,
+                display: self.display,
+                window: self.window,
+                message_type: (self.xlib.XInternAtom)(
+                    self.display,
+                    b"_NET_WM_DESKTOP\0".as_ptr() as *const _,
+                    xlib::False,
+                ),
+                format: 32,
+                data: xlib::ClientMessageData::new(),
+            };
+            message.data.set_long(0, desktop);
+            // Source indication 1 marks the request as coming from a normal application
+            message.data.set_long(1, 1);
+            let mut event = xlib::XEvent::from(message);
+            (self.xlib.XSendEvent)(
+                self.display,
+                root,
+                xlib::False,
+                xlib::SubstructureRedirectMask | xlib::SubstructureNotifyMask,
+                &mut event,
+            );
+            (self.xlib.XFlush)(self.display);
+        }
+    }
+
+    /// Marks the window as transient for `parent`, which window managers keep it above.
+    pub fn set_transient_for(&self, parent: &X11) {
+        unsafe {
+            (self.xlib.XSetTransientForHint)(self.display, self.window, parent.window);
+            (self.xlib.XFlush)(self.display);
+        }
+    }
+}
+
+/// Whether `window` is on X11, rather than Wayland.
+pub fn is_x11(window: &Window) -> bool {
+    window.xlib_window().is_some()
+}